@@ -0,0 +1,956 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{
+    errors::check_error::CheckError,
+    parsing::ast::{
+        BinaryOp, Expr, FunctionDecl, Literal, MatchArm, Pattern, Stmt, TypeAnnotation, UnaryOp,
+    },
+};
+
+//static types, anything the checker can't pin down is Any and passes every check
+#[derive(Debug, Clone, PartialEq)]
+enum Type {
+    Integer,
+    Float,
+    //either an integer or a float
+    Number,
+    String,
+    Boolean,
+    Null,
+    List,
+    Map,
+    //only annotated functions carry a signature
+    Function(Option<Rc<Signature>>),
+    //the class value itself, calling it builds an Instance
+    Class(String),
+    Instance(String),
+    Module,
+    Any,
+}
+
+#[derive(Debug, PartialEq)]
+struct Signature {
+    params: Vec<Type>,
+    ret: Type,
+}
+
+//an annotated binding holds its type for life, an unannotated one may be reassigned to anything
+#[derive(Clone)]
+struct Binding {
+    kind: Type,
+    declared: bool,
+}
+
+#[derive(Default)]
+struct ClassInfo {
+    superclass: Option<String>,
+    //annotated methods only
+    methods: HashMap<String, Rc<Signature>>,
+}
+
+struct Checker {
+    //the first scope holds the globals
+    scopes: Vec<HashMap<String, Binding>>,
+    //every class declared anywhere in the file plus the imported names, usable as annotations
+    classes: HashMap<String, ClassInfo>,
+    //return type of each enclosing function, None where it is unannotated
+    returns: Vec<Option<Type>>,
+    current_class: Option<String>,
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Integer => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::Number => write!(f, "number"),
+            Type::String => write!(f, "string"),
+            Type::Boolean => write!(f, "bool"),
+            Type::Null => write!(f, "null"),
+            Type::List => write!(f, "list"),
+            Type::Map => write!(f, "map"),
+            Type::Function(Some(signature)) => {
+                let params: Vec<String> = signature.params.iter().map(|p| p.to_string()).collect();
+                write!(f, "fun({}): {}", params.join(", "), signature.ret)
+            }
+            Type::Function(None) => write!(f, "function"),
+            Type::Class(name) => write!(f, "class {}", name),
+            Type::Instance(name) => write!(f, "{}", name),
+            Type::Module => write!(f, "module"),
+            Type::Any => write!(f, "any"),
+        }
+    }
+}
+
+impl Type {
+    fn is_numeric(&self) -> bool {
+        matches!(self, Type::Integer | Type::Float | Type::Number)
+    }
+}
+
+fn is_annotated(declaration: &FunctionDecl) -> bool {
+    declaration.return_type.is_some() || declaration.param_types.iter().any(|t| t.is_some())
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        let mut classes: HashMap<String, ClassInfo> = HashMap::new();
+        //the builtin class caught runtime errors are turned into
+        classes.insert("Error".to_string(), ClassInfo::default());
+        Checker {
+            scopes: vec![HashMap::new()],
+            classes,
+            returns: Vec::new(),
+            current_class: None,
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn define(&mut self, name: &str, kind: Type, declared: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), Binding { kind, declared });
+        }
+    }
+
+    //names not found in any scope are globals defined further down or builtins, so they're Any
+    fn lookup(&self, name: &str) -> Option<&Binding> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    //classes are collected up front so an annotation can name a class declared further down. this
+    //visits every statement body check does, a class declared inside a lambda is only registered
+    //once check reaches it
+    fn collect_classes<'a>(
+        &mut self,
+        statements: &'a Vec<Stmt>,
+        found: &mut Vec<(&'a String, &'a Vec<Rc<FunctionDecl>>)>,
+    ) {
+        for statement in statements {
+            self.collect_class(statement, found);
+        }
+    }
+
+    fn collect_class<'a>(
+        &mut self,
+        statement: &'a Stmt,
+        found: &mut Vec<(&'a String, &'a Vec<Rc<FunctionDecl>>)>,
+    ) {
+        match statement {
+            Stmt::Class {
+                name,
+                superclass,
+                methods,
+                ..
+            } => {
+                let superclass = match superclass {
+                    Some(Expr::Variable { name, .. }) => Some(name.to_string()),
+                    _ => None,
+                };
+                self.classes.insert(
+                    name.to_string(),
+                    ClassInfo {
+                        superclass,
+                        methods: HashMap::new(),
+                    },
+                );
+                found.push((name, methods));
+                for method in methods {
+                    self.collect_classes(&method.body, found);
+                }
+            }
+            //imported names could be classes, nothing is known about them beyond the name
+            Stmt::Import { names, .. } => {
+                for name in names {
+                    self.classes.insert(name.to_string(), ClassInfo::default());
+                }
+            }
+            Stmt::Block(body) => self.collect_classes(body, found),
+            Stmt::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.collect_class(then_branch, found);
+                if let Some(else_branch) = else_branch {
+                    self.collect_class(else_branch, found);
+                }
+            }
+            Stmt::While { body, .. } => self.collect_class(body, found),
+            Stmt::Function(declaration) => self.collect_classes(&declaration.body, found),
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                self.collect_classes(body, found);
+                if let Some((_, catch_body)) = catch {
+                    self.collect_classes(catch_body, found);
+                }
+                if let Some(finally) = finally {
+                    self.collect_classes(finally, found);
+                }
+            }
+            Stmt::Print(_)
+            | Stmt::Expression(_)
+            | Stmt::Var { .. }
+            | Stmt::Break { .. }
+            | Stmt::Continue { .. }
+            | Stmt::Return { .. }
+            | Stmt::Throw { .. } => (),
+        }
+    }
+
+    //run once every class name is known, since a method may be annotated with any of them
+    fn collect_methods(
+        &mut self,
+        found: &Vec<(&String, &Vec<Rc<FunctionDecl>>)>,
+    ) -> Result<(), CheckError> {
+        for (name, methods) in found {
+            for method in methods.iter() {
+                if let Some(signature) = self.signature(method)?
+                    && let Some(class) = self.classes.get_mut(*name)
+                {
+                    class.methods.insert(method.name.to_string(), signature);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn annotation_type(
+        &self,
+        annotation: &TypeAnnotation,
+        line: usize,
+    ) -> Result<Type, CheckError> {
+        Ok(match annotation {
+            TypeAnnotation::Integer => Type::Integer,
+            TypeAnnotation::Float => Type::Float,
+            TypeAnnotation::Number => Type::Number,
+            TypeAnnotation::String => Type::String,
+            TypeAnnotation::Boolean => Type::Boolean,
+            TypeAnnotation::Null => Type::Null,
+            TypeAnnotation::List => Type::List,
+            TypeAnnotation::Map => Type::Map,
+            TypeAnnotation::Function => Type::Function(None),
+            TypeAnnotation::Any => Type::Any,
+            TypeAnnotation::Class(name) => match self.classes.contains_key(name) {
+                true => Type::Instance(name.to_string()),
+                false => {
+                    return Err(CheckError::UnknownType {
+                        name: name.to_string(),
+                        line,
+                    });
+                }
+            },
+        })
+    }
+
+    //None for a function with no annotations at all, which stays fully dynamic
+    fn signature(&self, declaration: &FunctionDecl) -> Result<Option<Rc<Signature>>, CheckError> {
+        if !is_annotated(declaration) {
+            return Ok(None);
+        }
+        let mut params: Vec<Type> = Vec::new();
+        for param_type in declaration.param_types.iter() {
+            params.push(match param_type {
+                Some(param_type) => self.annotation_type(param_type, declaration.line)?,
+                None => Type::Any,
+            });
+        }
+        let ret: Type = match &declaration.return_type {
+            Some(return_type) => self.annotation_type(return_type, declaration.line)?,
+            None => Type::Any,
+        };
+        Ok(Some(Rc::new(Signature { params, ret })))
+    }
+
+    fn is_subclass(&self, name: &str, target: &str) -> bool {
+        let mut current: Option<&str> = Some(name);
+        //bounded in case the superclasses loop, which the resolver only rules out directly
+        for _ in 0..=self.classes.len() {
+            match current {
+                Some(class) if class == target => return true,
+                Some(class) => {
+                    current = self
+                        .classes
+                        .get(class)
+                        .and_then(|info| info.superclass.as_deref())
+                }
+                None => return false,
+            }
+        }
+        false
+    }
+
+    fn find_method(&self, class: &str, name: &str) -> Option<Rc<Signature>> {
+        let mut current: Option<&str> = Some(class);
+        for _ in 0..=self.classes.len() {
+            let info = self.classes.get(current?)?;
+            if let Some(signature) = info.methods.get(name) {
+                return Some(Rc::clone(signature));
+            }
+            current = info.superclass.as_deref();
+        }
+        None
+    }
+
+    fn assignable(&self, expected: &Type, found: &Type) -> bool {
+        match (expected, found) {
+            (Type::Any, _) | (_, Type::Any) => true,
+            (Type::Number, found) => found.is_numeric(),
+            (Type::Float, Type::Integer) => true,
+            (Type::Function(_), Type::Function(_)) => true,
+            (Type::Instance(expected), Type::Instance(found)) => self.is_subclass(found, expected),
+            (expected, found) => expected == found,
+        }
+    }
+
+    fn expect(&self, expected: &Type, found: &Type, line: usize) -> Result<(), CheckError> {
+        match self.assignable(expected, found) {
+            true => Ok(()),
+            false => Err(CheckError::TypeMismatch {
+                expected: expected.to_string(),
+                found: found.to_string(),
+                line,
+            }),
+        }
+    }
+
+    //mirrors eval_binary: integers stay integers, floats are contagious and + also joins strings.
+    //an Any operand only rules out operands that could never work, the result stays Any
+    fn binary_type(
+        &self,
+        op: &BinaryOp,
+        left: &Type,
+        right: &Type,
+        line: usize,
+    ) -> Result<Type, CheckError> {
+        let invalid = || CheckError::InvalidOperands {
+            op: op.to_string(),
+            left: left.to_string(),
+            right: right.to_string(),
+            line,
+        };
+        let numeric_or_any = |t: &Type| t.is_numeric() || *t == Type::Any;
+
+        match op {
+            BinaryOp::Plus
+            | BinaryOp::Minus
+            | BinaryOp::Star
+            | BinaryOp::Slash
+            | BinaryOp::Percent
+            | BinaryOp::StarStar => {
+                let plus: bool = matches!(op, BinaryOp::Plus);
+                let operand = |t: &Type| numeric_or_any(t) || (plus && *t == Type::String);
+                match (left, right) {
+                    (Type::Any, _) | (_, Type::Any) if operand(left) && operand(right) => {
+                        Ok(Type::Any)
+                    }
+                    (Type::String, Type::String) if plus => Ok(Type::String),
+                    (Type::Float, _) | (_, Type::Float)
+                        if left.is_numeric() && right.is_numeric() =>
+                    {
+                        Ok(Type::Float)
+                    }
+                    //a negative exponent turns integers into a float
+                    (Type::Integer, Type::Integer) if !matches!(op, BinaryOp::StarStar) => {
+                        Ok(Type::Integer)
+                    }
+                    _ if left.is_numeric() && right.is_numeric() => Ok(Type::Number),
+                    _ => Err(invalid()),
+                }
+            }
+            BinaryOp::Ampersand
+            | BinaryOp::Pipe
+            | BinaryOp::Caret
+            | BinaryOp::LessLess
+            | BinaryOp::GreaterGreater => match (left, right) {
+                (Type::Integer, Type::Integer) => Ok(Type::Integer),
+                (Type::Integer | Type::Any, Type::Integer | Type::Any) => Ok(Type::Any),
+                _ => Err(invalid()),
+            },
+            BinaryOp::GreaterThan
+            | BinaryOp::GreaterEqual
+            | BinaryOp::LessThan
+            | BinaryOp::LessEqual => match numeric_or_any(left) && numeric_or_any(right) {
+                true => Ok(Type::Boolean),
+                false => Err(invalid()),
+            },
+            //only numbers, strings and booleans can be compared for equality
+            BinaryOp::EqualEqual | BinaryOp::BangEqual => match (left, right) {
+                (Type::Any, _) | (_, Type::Any) => Ok(Type::Boolean),
+                (left, right) if left.is_numeric() && right.is_numeric() => Ok(Type::Boolean),
+                (Type::String, Type::String) | (Type::Boolean, Type::Boolean) => Ok(Type::Boolean),
+                _ => Err(invalid()),
+            },
+            BinaryOp::Equal | BinaryOp::And | BinaryOp::Or => Ok(Type::Any),
+        }
+    }
+
+    fn unary_type(&self, op: &UnaryOp, right: &Type, line: usize) -> Result<Type, CheckError> {
+        let invalid = || CheckError::InvalidOperand {
+            op: op.to_string(),
+            operand: right.to_string(),
+            line,
+        };
+        match op {
+            UnaryOp::Bang => Ok(Type::Boolean),
+            UnaryOp::Minus => match right {
+                Type::Any => Ok(Type::Any),
+                right if right.is_numeric() => Ok(right.clone()),
+                _ => Err(invalid()),
+            },
+            UnaryOp::Tilde => match right {
+                Type::Integer | Type::Any => Ok(right.clone()),
+                _ => Err(invalid()),
+            },
+        }
+    }
+
+    //assignments through a variable have to keep an annotated variable's type
+    fn check_assignment(&self, name: &str, found: &Type, line: usize) -> Result<(), CheckError> {
+        match self.lookup(name) {
+            Some(binding) if binding.declared => self.expect(&binding.kind, found, line),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_arguments(
+        &self,
+        signature: &Signature,
+        arguments: &[Type],
+        line: usize,
+    ) -> Result<(), CheckError> {
+        if signature.params.len() != arguments.len() {
+            return Err(CheckError::InvalidArgumentCount {
+                expected: signature.params.len(),
+                found: arguments.len(),
+                line,
+            });
+        }
+        for (param, argument) in signature.params.iter().zip(arguments.iter()) {
+            self.expect(param, argument, line)?;
+        }
+        Ok(())
+    }
+
+    //the body is checked in its own scope with each param bound to its annotated type
+    fn check_function(
+        &mut self,
+        declaration: &FunctionDecl,
+        signature: &Option<Rc<Signature>>,
+        initializer: bool,
+    ) -> Result<(), CheckError> {
+        self.begin_scope();
+        for (i, param) in declaration.params.iter().enumerate() {
+            let kind: Type = match signature {
+                Some(signature) => signature.params[i].clone(),
+                None => Type::Any,
+            };
+            self.define(param, kind, declaration.param_types[i].is_some());
+        }
+        let ret: Option<Type> = match &declaration.return_type {
+            Some(_) => signature.as_ref().map(|signature| signature.ret.clone()),
+            None => None,
+        };
+        self.returns.push(ret.clone());
+        let result = self.check_statements(&declaration.body);
+        self.returns.pop();
+        self.end_scope();
+        result?;
+
+        //running off the end returns null just like a bare return
+        match ret {
+            Some(ret) if !initializer && can_complete(&declaration.body) => {
+                self.expect(&ret, &Type::Null, declaration.line)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_class(
+        &mut self,
+        name: &str,
+        superclass: &Option<Expr>,
+        methods: &Vec<Rc<FunctionDecl>>,
+    ) -> Result<(), CheckError> {
+        if let Some(superclass) = superclass {
+            self.check_expr(superclass)?;
+        }
+        self.define(name, Type::Class(name.to_string()), false);
+        //classes in a lambda body aren't collected up front
+        self.classes.entry(name.to_string()).or_default();
+
+        let enclosing_class = self.current_class.replace(name.to_string());
+        let mut result = Ok(());
+        for method in methods {
+            result = self.signature(method).and_then(|signature| {
+                self.check_function(method, &signature, method.name == "init")
+            });
+            if result.is_err() {
+                break;
+            }
+        }
+        self.current_class = enclosing_class;
+        result
+    }
+
+    fn bind_pattern(&mut self, pattern: &Pattern) -> Result<(), CheckError> {
+        match pattern {
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Range { .. } => Ok(()),
+            Pattern::Binding(name) => {
+                self.define(name, Type::Any, false);
+                Ok(())
+            }
+            Pattern::List { elements, rest } => {
+                for element in elements {
+                    self.bind_pattern(element)?;
+                }
+                match rest {
+                    Some(rest) => self.bind_pattern(rest),
+                    None => Ok(()),
+                }
+            }
+            Pattern::Map(entries) => {
+                for (_, pattern) in entries {
+                    self.bind_pattern(pattern)?;
+                }
+                Ok(())
+            }
+            Pattern::Instance { class, fields, .. } => {
+                self.check_expr(class)?;
+                for (_, pattern) in fields {
+                    self.bind_pattern(pattern)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn check_match_arm(&mut self, arm: &MatchArm) -> Result<Type, CheckError> {
+        self.begin_scope();
+        let mut result = self.bind_pattern(&arm.pattern);
+        if result.is_ok()
+            && let Some(guard) = &arm.guard
+        {
+            result = self.check_expr(guard).map(|_| ());
+        }
+        let result = result.and_then(|_| self.check_expr(&arm.body));
+        self.end_scope();
+        result
+    }
+
+    fn check_statements(&mut self, statements: &Vec<Stmt>) -> Result<(), CheckError> {
+        for statement in statements {
+            self.check_stmt(statement)?;
+        }
+        Ok(())
+    }
+
+    fn check_stmt_block(&mut self, statements: &Vec<Stmt>) -> Result<(), CheckError> {
+        self.begin_scope();
+        let result = self.check_statements(statements);
+        self.end_scope();
+        result
+    }
+
+    fn check_stmt(&mut self, stmt: &Stmt) -> Result<(), CheckError> {
+        match stmt {
+            Stmt::Print(e) | Stmt::Expression(e) | Stmt::Throw { value: e, .. } => {
+                self.check_expr(e).map(|_| ())
+            }
+            Stmt::Var {
+                name,
+                initializer,
+                mutable,
+                annotation,
+                line,
+            } => {
+                let found: Option<Type> = match initializer {
+                    Some(initializer) => Some(self.check_expr(initializer)?),
+                    None => None,
+                };
+                match annotation {
+                    Some(annotation) => {
+                        let kind: Type = self.annotation_type(annotation, *line)?;
+                        if let Some(found) = &found {
+                            self.expect(&kind, found, *line)?;
+                        }
+                        self.define(name, kind, true);
+                    }
+                    //a constant can never change so it keeps the type of its initializer
+                    None if !*mutable => self.define(name, found.unwrap_or(Type::Any), true),
+                    None => self.define(name, Type::Any, false),
+                }
+                Ok(())
+            }
+            Stmt::Block(statements) => self.check_stmt_block(statements),
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.check_expr(condition)?;
+                self.check_stmt(then_branch)?;
+                match else_branch {
+                    Some(else_branch) => self.check_stmt(else_branch),
+                    None => Ok(()),
+                }
+            }
+            Stmt::While {
+                condition,
+                body,
+                increment,
+                ..
+            } => {
+                self.check_expr(condition)?;
+                self.check_stmt(body)?;
+                match increment {
+                    Some(increment) => self.check_expr(increment).map(|_| ()),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Break { .. } | Stmt::Continue { .. } => Ok(()),
+            Stmt::Function(declaration) => {
+                //defined before the body so recursive calls are checked against the signature
+                let signature = self.signature(declaration)?;
+                let kind: Type = match &signature {
+                    Some(signature) => Type::Function(Some(Rc::clone(signature))),
+                    None => Type::Any,
+                };
+                self.define(&declaration.name, kind, signature.is_some());
+                self.check_function(declaration, &signature, false)
+            }
+            Stmt::Return { value, line } => {
+                let found: Type = match value {
+                    Some(value) => self.check_expr(value)?,
+                    None => Type::Null,
+                };
+                match self.returns.last() {
+                    Some(Some(expected)) => self.expect(expected, &found, *line),
+                    _ => Ok(()),
+                }
+            }
+            Stmt::Import { alias, names, .. } => {
+                if let Some(alias) = alias {
+                    self.define(alias, Type::Module, false);
+                }
+                for name in names {
+                    self.define(name, Type::Any, false);
+                }
+                Ok(())
+            }
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                self.check_stmt_block(body)?;
+                if let Some((name, catch_body)) = catch {
+                    self.begin_scope();
+                    self.define(name, Type::Any, false);
+                    let result = self.check_statements(catch_body);
+                    self.end_scope();
+                    result?;
+                }
+                match finally {
+                    Some(finally) => self.check_stmt_block(finally),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Class {
+                name,
+                superclass,
+                methods,
+                ..
+            } => self.check_class(name, superclass, methods),
+        }
+    }
+
+    fn check_expr(&mut self, expr: &Expr) -> Result<Type, CheckError> {
+        match expr {
+            Expr::Binary {
+                left,
+                op,
+                right,
+                line,
+            } => {
+                let left = self.check_expr(left)?;
+                let right = self.check_expr(right)?;
+                self.binary_type(op, &left, &right, *line)
+            }
+            //either operand may be the result
+            Expr::Logical { left, right, .. } => {
+                let left = self.check_expr(left)?;
+                let right = self.check_expr(right)?;
+                Ok(if left == right { left } else { Type::Any })
+            }
+            Expr::Match { subject, arms, .. } => {
+                self.check_expr(subject)?;
+                let mut kinds: Vec<Type> = Vec::new();
+                for arm in arms {
+                    kinds.push(self.check_match_arm(arm)?);
+                }
+                Ok(match kinds.first() {
+                    Some(first) if kinds.iter().all(|kind| kind == first) => first.clone(),
+                    _ => Type::Any,
+                })
+            }
+            Expr::Lambda(declaration) => {
+                let signature = self.signature(declaration)?;
+                self.check_function(declaration, &signature, false)?;
+                Ok(Type::Function(signature))
+            }
+            Expr::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.check_expr(condition)?;
+                let then_type = self.check_expr(then_branch)?;
+                let else_type = self.check_expr(else_branch)?;
+                Ok(if then_type == else_type {
+                    then_type
+                } else {
+                    Type::Any
+                })
+            }
+            Expr::Unary { op, right, line } => {
+                let right = self.check_expr(right)?;
+                self.unary_type(op, &right, *line)
+            }
+            Expr::Grouping { exp } => self.check_expr(exp),
+            Expr::Literal(literal) => Ok(match literal {
+                Literal::Integer(_) => Type::Integer,
+                Literal::Number(_) => Type::Float,
+                Literal::StringLiteral(_) => Type::String,
+                Literal::True | Literal::False => Type::Boolean,
+                Literal::Null => Type::Null,
+            }),
+            Expr::Variable { name, .. } => Ok(match self.lookup(name) {
+                Some(binding) => binding.kind.clone(),
+                None => Type::Any,
+            }),
+            Expr::Assign {
+                name, value, line, ..
+            } => {
+                let found = self.check_expr(value)?;
+                self.check_assignment(name, &found, *line)?;
+                Ok(found)
+            }
+            Expr::Call {
+                callee,
+                arguments,
+                line,
+            } => {
+                let callee = self.check_expr(callee)?;
+                let mut found: Vec<Type> = Vec::new();
+                for argument in arguments {
+                    found.push(self.check_expr(argument)?);
+                }
+                match callee {
+                    Type::Function(Some(signature)) => {
+                        self.check_arguments(&signature, &found, *line)?;
+                        Ok(signature.ret.clone())
+                    }
+                    Type::Class(name) => {
+                        if let Some(signature) = self.find_method(&name, "init") {
+                            self.check_arguments(&signature, &found, *line)?;
+                        }
+                        Ok(Type::Instance(name))
+                    }
+                    Type::Function(None) | Type::Any => Ok(Type::Any),
+                    callee => Err(CheckError::NotCallable {
+                        found: callee.to_string(),
+                        line: *line,
+                    }),
+                }
+            }
+            //fields aren't tracked, only annotated methods
+            Expr::Get { object, name, .. } => match self.check_expr(object)? {
+                Type::Instance(class) => Ok(match self.find_method(&class, name) {
+                    Some(signature) => Type::Function(Some(signature)),
+                    None => Type::Any,
+                }),
+                _ => Ok(Type::Any),
+            },
+            Expr::Set { object, value, .. } => {
+                let found = self.check_expr(value)?;
+                self.check_expr(object)?;
+                Ok(found)
+            }
+            Expr::Interpolation(parts) => {
+                for part in parts {
+                    self.check_expr(part)?;
+                }
+                Ok(Type::String)
+            }
+            Expr::List(elements) => {
+                for element in elements {
+                    self.check_expr(element)?;
+                }
+                Ok(Type::List)
+            }
+            Expr::Map { entries, .. } => {
+                for (key, value) in entries {
+                    self.check_expr(key)?;
+                    self.check_expr(value)?;
+                }
+                Ok(Type::Map)
+            }
+            Expr::Index {
+                object,
+                index,
+                line,
+            } => {
+                let object = self.check_expr(object)?;
+                self.check_expr(index)?;
+                self.check_indexable(&object, *line)?;
+                Ok(Type::Any)
+            }
+            Expr::IndexSet {
+                object,
+                index,
+                value,
+                line,
+            } => {
+                let object = self.check_expr(object)?;
+                self.check_expr(index)?;
+                let found = self.check_expr(value)?;
+                self.check_indexable(&object, *line)?;
+                Ok(found)
+            }
+            Expr::CompoundAssign {
+                target,
+                op,
+                value,
+                line,
+            } => {
+                let current = self.check_expr(target)?;
+                let found = self.check_expr(value)?;
+                let result = self.binary_type(op, &current, &found, *line)?;
+                if let Expr::Variable { name, .. } = target.as_ref() {
+                    self.check_assignment(name, &result, *line)?;
+                }
+                Ok(result)
+            }
+            Expr::Increment {
+                target,
+                op,
+                prefix,
+                line,
+            } => {
+                let current = self.check_expr(target)?;
+                let result = self.binary_type(op, &current, &Type::Integer, *line)?;
+                if let Expr::Variable { name, .. } = target.as_ref() {
+                    self.check_assignment(name, &result, *line)?;
+                }
+                Ok(if *prefix { result } else { current })
+            }
+            Expr::This { .. } => Ok(match &self.current_class {
+                Some(class) => Type::Instance(class.to_string()),
+                None => Type::Any,
+            }),
+            Expr::Super { .. } => Ok(Type::Any),
+        }
+    }
+
+    fn check_indexable(&self, object: &Type, line: usize) -> Result<(), CheckError> {
+        match object {
+            Type::List | Type::Map | Type::Any => Ok(()),
+            object => Err(CheckError::NotIndexable {
+                found: object.to_string(),
+                line,
+            }),
+        }
+    }
+}
+
+//whether running the statements can reach the end of them rather than always leaving through a
+//return, throw, break or continue
+pub(crate) fn can_complete(statements: &[Stmt]) -> bool {
+    statements.iter().all(completes)
+}
+
+fn completes(statement: &Stmt) -> bool {
+    match statement {
+        Stmt::Return { .. } | Stmt::Throw { .. } | Stmt::Break { .. } | Stmt::Continue { .. } => {
+            false
+        }
+        Stmt::Block(body) => can_complete(body),
+        Stmt::If {
+            then_branch,
+            else_branch: Some(else_branch),
+            ..
+        } => completes(then_branch) || completes(else_branch),
+        //while (true) only finishes through a break
+        Stmt::While {
+            condition: Expr::Literal(Literal::True),
+            body,
+            ..
+        } => breaks_out(body, 0),
+        Stmt::Try {
+            body,
+            catch,
+            finally,
+        } => {
+            let finishes: bool = can_complete(body)
+                || catch
+                    .as_ref()
+                    .is_some_and(|(_, catch_body)| can_complete(catch_body));
+            finishes && finally.as_deref().is_none_or(can_complete)
+        }
+        _ => true,
+    }
+}
+
+//whether a break can leave the loop the statement sits in. depth counts the loops nested inside
+//it, an unlabeled break there only leaves the inner loop
+fn breaks_out(statement: &Stmt, depth: usize) -> bool {
+    match statement {
+        Stmt::Break { label } => label.is_some() || depth == 0,
+        Stmt::Block(body) => body.iter().any(|s| breaks_out(s, depth)),
+        Stmt::If {
+            then_branch,
+            else_branch,
+            ..
+        } => {
+            breaks_out(then_branch, depth)
+                || else_branch
+                    .as_ref()
+                    .is_some_and(|else_branch| breaks_out(else_branch, depth))
+        }
+        Stmt::While { body, .. } => breaks_out(body, depth + 1),
+        Stmt::Try {
+            body,
+            catch,
+            finally,
+        } => {
+            body.iter().any(|s| breaks_out(s, depth))
+                || catch
+                    .as_ref()
+                    .is_some_and(|(_, catch_body)| catch_body.iter().any(|s| breaks_out(s, depth)))
+                || finally
+                    .as_ref()
+                    .is_some_and(|finally| finally.iter().any(|s| breaks_out(s, depth)))
+        }
+        _ => false,
+    }
+}
+
+//runs after resolving and before interpreting. only annotated names and literals have a known
+//type, so unannotated code passes unless an operation can never succeed
+pub fn check(statements: &Vec<Stmt>) -> Result<(), CheckError> {
+    let mut checker: Checker = Checker::new();
+    let mut classes: Vec<(&String, &Vec<Rc<FunctionDecl>>)> = Vec::new();
+    checker.collect_classes(statements, &mut classes);
+    checker.collect_methods(&classes)?;
+    checker.check_statements(statements)
+}
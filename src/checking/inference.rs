@@ -0,0 +1,1245 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use crate::{
+    checking::checker::can_complete,
+    errors::check_error::CheckError,
+    parsing::ast::{
+        BinaryOp, Expr, FunctionDecl, Literal, MatchArm, Pattern, Stmt, TypeAnnotation, UnaryOp,
+    },
+};
+
+//integers and floats share one type here since mixing them is always allowed at runtime. a null
+//literal fits wherever a value of any type is expected, Null is only what a function hands back
+//when it never returns a value
+#[derive(Debug, Clone, PartialEq)]
+enum Ty {
+    Var(usize),
+    Null,
+    Number,
+    String,
+    Boolean,
+    List(Box<Ty>),
+    Map(Box<Ty>, Box<Ty>),
+    Function(Vec<Ty>, Box<Ty>),
+    Class(String),
+    Instance(String),
+    Module,
+    //dynamically typed, unifies with everything
+    Any,
+}
+
+//a type generalized over vars, each use of the binding gets fresh copies of them
+#[derive(Debug, Clone)]
+struct Scheme {
+    vars: Vec<usize>,
+    ty: Ty,
+}
+
+//an operation that means something different depending on an operand that isn't known yet
+#[derive(Clone)]
+enum Deferred {
+    //a + b joins strings or adds numbers
+    Plus(Ty, Ty),
+    //object[index] reads a list or a map
+    Index(Ty, Ty),
+}
+
+//what the innermost function's return statements have produced so far
+struct Returns {
+    ty: Ty,
+    //a return with a value other than null
+    value: bool,
+    //a bare return or return null
+    null: bool,
+}
+
+#[derive(Default)]
+struct ClassTypes {
+    superclass: Option<String>,
+    //methods stay monomorphic since they can refer to each other through this
+    methods: HashMap<String, Ty>,
+    //filled in by the first this.field = value or read of the field
+    fields: HashMap<String, Ty>,
+}
+
+struct Inferrer {
+    //what each type var has been bound to so far
+    substitution: Vec<Option<Ty>>,
+    scopes: Vec<HashMap<String, Scheme>>,
+    classes: HashMap<String, ClassTypes>,
+    //one entry per enclosing function
+    returns: Vec<Returns>,
+    current_class: Option<String>,
+    //line of the last expression with one, given to unification errors
+    line: usize,
+    //top level bindings and methods in declaration order, formatted once everything is inferred
+    signatures: Vec<(String, Scheme)>,
+    //deferred operations with their result and line. settled once the operand they're waiting on
+    //is known, or left dynamic when the function they're in is done
+    pending: Vec<(Deferred, Ty, usize)>,
+}
+
+impl Scheme {
+    fn mono(ty: Ty) -> Self {
+        Scheme {
+            vars: Vec::new(),
+            ty,
+        }
+    }
+}
+
+//vars are named 'a, 'b, ... in the order they first appear
+fn format_type(ty: &Ty, names: &mut HashMap<usize, String>) -> String {
+    match ty {
+        Ty::Var(id) => {
+            let next: usize = names.len();
+            names
+                .entry(*id)
+                .or_insert_with(|| match next < 26 {
+                    true => format!("'{}", (b'a' + next as u8) as char),
+                    false => format!("'t{}", next),
+                })
+                .to_string()
+        }
+        Ty::Null => "null".to_string(),
+        Ty::Number => "number".to_string(),
+        Ty::String => "string".to_string(),
+        Ty::Boolean => "bool".to_string(),
+        Ty::List(element) => format!("list<{}>", format_type(element, names)),
+        Ty::Map(key, value) => format!(
+            "map<{}, {}>",
+            format_type(key, names),
+            format_type(value, names)
+        ),
+        Ty::Function(params, ret) => {
+            let params: Vec<String> = params.iter().map(|p| format_type(p, names)).collect();
+            format!("fun({}) -> {}", params.join(", "), format_type(ret, names))
+        }
+        Ty::Class(name) => format!("class {}", name),
+        Ty::Instance(name) => name.to_string(),
+        Ty::Module => "module".to_string(),
+        Ty::Any => "any".to_string(),
+    }
+}
+
+impl Inferrer {
+    pub fn new() -> Self {
+        let mut classes: HashMap<String, ClassTypes> = HashMap::new();
+        //Error(message) builds what a caught runtime error becomes
+        let error: Ty = Ty::Instance("Error".to_string());
+        classes.insert(
+            "Error".to_string(),
+            ClassTypes {
+                superclass: None,
+                methods: HashMap::from([(
+                    "init".to_string(),
+                    Ty::Function(vec![Ty::Any], Box::new(error)),
+                )]),
+                fields: HashMap::from([
+                    ("message".to_string(), Ty::Any),
+                    ("line".to_string(), Ty::Number),
+                ]),
+            },
+        );
+        let mut globals: HashMap<String, Scheme> = HashMap::new();
+        globals.insert(
+            "Error".to_string(),
+            Scheme::mono(Ty::Class("Error".to_string())),
+        );
+        Inferrer {
+            substitution: Vec::new(),
+            scopes: vec![globals],
+            classes,
+            returns: Vec::new(),
+            current_class: None,
+            line: 0,
+            signatures: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn fresh(&mut self) -> Ty {
+        self.substitution.push(None);
+        Ty::Var(self.substitution.len() - 1)
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn define(&mut self, name: &str, scheme: Scheme) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), scheme);
+        }
+    }
+
+    //unknown names are globals defined further down, imported or builtin, and stay dynamic
+    fn lookup(&mut self, name: &str) -> Ty {
+        match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+            Some(scheme) => {
+                let scheme: Scheme = scheme.clone();
+                self.instantiate(&scheme)
+            }
+            None => Ty::Any,
+        }
+    }
+
+    //follows var bindings until reaching an unbound var or a concrete type
+    fn resolve(&self, ty: &Ty) -> Ty {
+        let mut ty: Ty = ty.clone();
+        while let Ty::Var(id) = ty {
+            match &self.substitution[id] {
+                Some(bound) => ty = bound.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    //applies the substitution all the way down
+    fn zonk(&self, ty: &Ty) -> Ty {
+        match self.resolve(ty) {
+            Ty::List(element) => Ty::List(Box::new(self.zonk(&element))),
+            Ty::Map(key, value) => Ty::Map(Box::new(self.zonk(&key)), Box::new(self.zonk(&value))),
+            Ty::Function(params, ret) => Ty::Function(
+                params.iter().map(|p| self.zonk(p)).collect(),
+                Box::new(self.zonk(&ret)),
+            ),
+            ty => ty,
+        }
+    }
+
+    fn display(&self, ty: &Ty) -> String {
+        format_type(&self.zonk(ty), &mut HashMap::new())
+    }
+
+    fn free_vars(&self, ty: &Ty, vars: &mut Vec<usize>) {
+        match self.resolve(ty) {
+            Ty::Var(id) if !vars.contains(&id) => vars.push(id),
+            Ty::List(element) => self.free_vars(&element, vars),
+            Ty::Map(key, value) => {
+                self.free_vars(&key, vars);
+                self.free_vars(&value, vars);
+            }
+            Ty::Function(params, ret) => {
+                for param in params.iter() {
+                    self.free_vars(param, vars);
+                }
+                self.free_vars(&ret, vars);
+            }
+            _ => (),
+        }
+    }
+
+    //vars still free somewhere in scope or in a class may be bound later, so they can't be
+    //generalized
+    fn generalize(&self, ty: &Ty) -> Scheme {
+        let mut in_scope: Vec<usize> = Vec::new();
+        for scheme in self.scopes.iter().flat_map(|scope| scope.values()) {
+            let mut vars: Vec<usize> = Vec::new();
+            self.free_vars(&scheme.ty, &mut vars);
+            in_scope.extend(vars.into_iter().filter(|var| !scheme.vars.contains(var)));
+        }
+        for types in self.classes.values() {
+            for ty in types.methods.values().chain(types.fields.values()) {
+                self.free_vars(ty, &mut in_scope);
+            }
+        }
+        let in_scope: HashSet<usize> = in_scope.into_iter().collect();
+
+        let mut vars: Vec<usize> = Vec::new();
+        self.free_vars(ty, &mut vars);
+        Scheme {
+            vars: vars
+                .into_iter()
+                .filter(|var| !in_scope.contains(var))
+                .collect(),
+            ty: self.zonk(ty),
+        }
+    }
+
+    fn instantiate(&mut self, scheme: &Scheme) -> Ty {
+        let mut fresh: HashMap<usize, Ty> = HashMap::new();
+        for var in scheme.vars.iter() {
+            let ty: Ty = self.fresh();
+            fresh.insert(*var, ty);
+        }
+        self.replace_vars(&self.zonk(&scheme.ty), &fresh)
+    }
+
+    fn replace_vars(&self, ty: &Ty, fresh: &HashMap<usize, Ty>) -> Ty {
+        match ty {
+            Ty::Var(id) => fresh.get(id).cloned().unwrap_or(Ty::Var(*id)),
+            Ty::List(element) => Ty::List(Box::new(self.replace_vars(element, fresh))),
+            Ty::Map(key, value) => Ty::Map(
+                Box::new(self.replace_vars(key, fresh)),
+                Box::new(self.replace_vars(value, fresh)),
+            ),
+            Ty::Function(params, ret) => Ty::Function(
+                params.iter().map(|p| self.replace_vars(p, fresh)).collect(),
+                Box::new(self.replace_vars(ret, fresh)),
+            ),
+            ty => ty.clone(),
+        }
+    }
+
+    fn occurs(&self, id: usize, ty: &Ty) -> bool {
+        let mut vars: Vec<usize> = Vec::new();
+        self.free_vars(ty, &mut vars);
+        vars.contains(&id)
+    }
+
+    fn is_subclass(&self, name: &str, target: &str) -> bool {
+        let mut current: Option<&str> = Some(name);
+        for _ in 0..=self.classes.len() {
+            match current {
+                Some(class) if class == target => return true,
+                Some(class) => {
+                    current = self
+                        .classes
+                        .get(class)
+                        .and_then(|types| types.superclass.as_deref())
+                }
+                None => return false,
+            }
+        }
+        false
+    }
+
+    fn bind(&mut self, id: usize, ty: Ty) -> Result<(), CheckError> {
+        if self.occurs(id, &ty) {
+            return Err(CheckError::RecursiveType {
+                var: self.display(&Ty::Var(id)),
+                kind: self.display(&ty),
+                line: self.line,
+            });
+        }
+        self.substitution[id] = Some(ty);
+        Ok(())
+    }
+
+    fn unify(&mut self, expected: &Ty, found: &Ty) -> Result<(), CheckError> {
+        let expected: Ty = self.resolve(expected);
+        let found: Ty = self.resolve(found);
+        match (&expected, &found) {
+            (Ty::Var(a), Ty::Var(b)) if a == b => Ok(()),
+            (Ty::Var(id), ty) | (ty, Ty::Var(id)) => self.bind(*id, ty.clone()),
+            (Ty::Any, _) | (_, Ty::Any) => Ok(()),
+            (Ty::Null, Ty::Null)
+            | (Ty::Number, Ty::Number)
+            | (Ty::String, Ty::String)
+            | (Ty::Boolean, Ty::Boolean)
+            | (Ty::Module, Ty::Module) => Ok(()),
+            (Ty::List(a), Ty::List(b)) => self.unify(a, b),
+            (Ty::Map(k1, v1), Ty::Map(k2, v2)) => {
+                self.unify(k1, k2)?;
+                self.unify(v1, v2)
+            }
+            (Ty::Function(p1, r1), Ty::Function(p2, r2)) => {
+                if p1.len() != p2.len() {
+                    return Err(CheckError::InvalidArgumentCount {
+                        expected: p1.len(),
+                        found: p2.len(),
+                        line: self.line,
+                    });
+                }
+                for (a, b) in p1.iter().zip(p2.iter()) {
+                    self.unify(a, b)?;
+                }
+                self.unify(r1, r2)
+            }
+            (Ty::Class(a), Ty::Class(b)) if a == b => Ok(()),
+            //a subclass instance can stand in for its superclass either way round
+            (Ty::Instance(a), Ty::Instance(b))
+                if self.is_subclass(a, b) || self.is_subclass(b, a) =>
+            {
+                Ok(())
+            }
+            _ => Err(CheckError::TypeMismatch {
+                expected: self.display(&expected),
+                found: self.display(&found),
+                line: self.line,
+            }),
+        }
+    }
+
+    fn annotation_type(&mut self, annotation: &TypeAnnotation) -> Ty {
+        match annotation {
+            TypeAnnotation::Integer | TypeAnnotation::Float | TypeAnnotation::Number => Ty::Number,
+            TypeAnnotation::String => Ty::String,
+            TypeAnnotation::Boolean => Ty::Boolean,
+            TypeAnnotation::List => Ty::List(Box::new(self.fresh())),
+            TypeAnnotation::Map => Ty::Map(Box::new(self.fresh()), Box::new(self.fresh())),
+            //null and function of unknown arity say nothing more specific than a fresh var
+            TypeAnnotation::Null | TypeAnnotation::Function => self.fresh(),
+            TypeAnnotation::Any => Ty::Any,
+            TypeAnnotation::Class(name) => Ty::Instance(name.to_string()),
+        }
+    }
+
+    fn literal_type(&mut self, literal: &Literal) -> Ty {
+        match literal {
+            Literal::Integer(_) | Literal::Number(_) => Ty::Number,
+            Literal::StringLiteral(_) => Ty::String,
+            Literal::True | Literal::False => Ty::Boolean,
+            Literal::Null => self.fresh(),
+        }
+    }
+
+    //+ joins strings or adds numbers, so both sides take the type of whichever is known first
+    fn plus_type(&mut self, left: &Ty, right: &Ty) -> Result<Ty, CheckError> {
+        let left_resolved: Ty = self.resolve(left);
+        let right_resolved: Ty = self.resolve(right);
+        if let (Ty::Var(_), Ty::Var(_)) = (&left_resolved, &right_resolved) {
+            return Ok(self.defer(Deferred::Plus(left.clone(), right.clone())));
+        }
+        if left_resolved == Ty::String || right_resolved == Ty::String {
+            self.unify(&Ty::String, left)?;
+            self.unify(&Ty::String, right)?;
+            return Ok(Ty::String);
+        }
+        if left_resolved == Ty::Any || right_resolved == Ty::Any {
+            return Ok(Ty::Any);
+        }
+        self.unify(&Ty::Number, left)?;
+        self.unify(&Ty::Number, right)?;
+        Ok(Ty::Number)
+    }
+
+    fn defer(&mut self, deferred: Deferred) -> Ty {
+        let result: Ty = self.fresh();
+        self.pending.push((deferred, result.clone(), self.line));
+        result
+    }
+
+    fn is_blocked(&self, deferred: &Deferred) -> bool {
+        match deferred {
+            Deferred::Plus(left, right) => {
+                matches!(
+                    (self.resolve(left), self.resolve(right)),
+                    (Ty::Var(_), Ty::Var(_))
+                )
+            }
+            Deferred::Index(object, _) => matches!(self.resolve(object), Ty::Var(_)),
+        }
+    }
+
+    //settles every pending operation from index start on whose operands have since become known.
+    //the rest could still go either way, so their results are left dynamic
+    fn finish_pending(&mut self, start: usize) -> Result<(), CheckError> {
+        let mut progress: bool = true;
+        while progress {
+            progress = false;
+            let mut i: usize = start;
+            while i < self.pending.len() {
+                let (deferred, result, line) = self.pending[i].clone();
+                if self.is_blocked(&deferred) {
+                    i += 1;
+                    continue;
+                }
+                self.pending.remove(i);
+                self.line = line;
+                let ty: Ty = match deferred {
+                    Deferred::Plus(left, right) => self.plus_type(&left, &right)?,
+                    Deferred::Index(object, index) => self.index_type(&object, &index)?,
+                };
+                self.unify(&result, &ty)?;
+                progress = true;
+            }
+        }
+        for (_, result, _) in self.pending.split_off(start) {
+            self.unify(&result, &Ty::Any)?;
+        }
+        Ok(())
+    }
+
+    fn binary_type(&mut self, op: &BinaryOp, left: &Ty, right: &Ty) -> Result<Ty, CheckError> {
+        match op {
+            BinaryOp::Plus => self.plus_type(left, right),
+            BinaryOp::Minus
+            | BinaryOp::Star
+            | BinaryOp::Slash
+            | BinaryOp::Percent
+            | BinaryOp::StarStar
+            | BinaryOp::Ampersand
+            | BinaryOp::Pipe
+            | BinaryOp::Caret
+            | BinaryOp::LessLess
+            | BinaryOp::GreaterGreater => {
+                self.unify(&Ty::Number, left)?;
+                self.unify(&Ty::Number, right)?;
+                Ok(Ty::Number)
+            }
+            BinaryOp::GreaterThan
+            | BinaryOp::GreaterEqual
+            | BinaryOp::LessThan
+            | BinaryOp::LessEqual => {
+                self.unify(&Ty::Number, left)?;
+                self.unify(&Ty::Number, right)?;
+                Ok(Ty::Boolean)
+            }
+            BinaryOp::EqualEqual | BinaryOp::BangEqual => {
+                self.unify(left, right)?;
+                Ok(Ty::Boolean)
+            }
+            BinaryOp::Equal | BinaryOp::And | BinaryOp::Or => {
+                self.unify(left, right)?;
+                Ok(left.clone())
+            }
+        }
+    }
+
+    fn find_method(&self, class: &str, name: &str) -> Option<Ty> {
+        let mut current: Option<&str> = Some(class);
+        for _ in 0..=self.classes.len() {
+            let types = self.classes.get(current?)?;
+            if let Some(method) = types.methods.get(name) {
+                return Some(method.clone());
+            }
+            current = types.superclass.as_deref();
+        }
+        None
+    }
+
+    //a field read before it's ever written still gets a var so later writes agree with it
+    fn property_type(&mut self, class: &str, name: &str) -> Ty {
+        if let Some(method) = self.find_method(class, name) {
+            return method;
+        }
+        let mut current: Option<String> = Some(class.to_string());
+        for _ in 0..=self.classes.len() {
+            let Some(name_of) = current else { break };
+            let Some(types) = self.classes.get(&name_of) else {
+                break;
+            };
+            if let Some(field) = types.fields.get(name) {
+                return field.clone();
+            }
+            current = types.superclass.clone();
+        }
+        let field: Ty = self.fresh();
+        if let Some(types) = self.classes.get_mut(class) {
+            types.fields.insert(name.to_string(), field.clone());
+        }
+        field
+    }
+
+    fn index_type(&mut self, object: &Ty, index: &Ty) -> Result<Ty, CheckError> {
+        match self.resolve(object) {
+            Ty::Any => Ok(Ty::Any),
+            Ty::Map(key, value) => {
+                self.unify(&key, index)?;
+                Ok(*value)
+            }
+            Ty::Var(_) => Ok(self.defer(Deferred::Index(object.clone(), index.clone()))),
+            //anything else indexed has to be a list
+            _ => {
+                let element: Ty = self.fresh();
+                self.unify(&Ty::List(Box::new(element.clone())), object)?;
+                self.unify(&Ty::Number, index)?;
+                Ok(element)
+            }
+        }
+    }
+
+    //a function that only ever returns null has a null return type. one that also returns values
+    //has their type, since null fits anywhere just like the null literal. init always hands back
+    //the instance
+    fn infer_function(
+        &mut self,
+        declaration: &FunctionDecl,
+        initializer: bool,
+    ) -> Result<Ty, CheckError> {
+        self.line = declaration.line;
+        let pending_start: usize = self.pending.len();
+        self.begin_scope();
+        let mut params: Vec<Ty> = Vec::new();
+        for (param, annotation) in declaration
+            .params
+            .iter()
+            .zip(declaration.param_types.iter())
+        {
+            let ty: Ty = match annotation {
+                Some(annotation) => self.annotation_type(annotation),
+                None => self.fresh(),
+            };
+            self.define(param, Scheme::mono(ty.clone()));
+            params.push(ty);
+        }
+        let ret: Ty = match &declaration.return_type {
+            Some(annotation) if !initializer => self.annotation_type(annotation),
+            _ => self.fresh(),
+        };
+        self.returns.push(Returns {
+            ty: ret.clone(),
+            value: false,
+            null: false,
+        });
+        let result = self.infer_statements(&declaration.body);
+        let returns: Option<Returns> = self.returns.pop();
+        self.end_scope();
+        result?;
+        self.finish_pending(pending_start)?;
+
+        if initializer {
+            let class: String = self.current_class.clone().unwrap_or_default();
+            return Ok(Ty::Function(params, Box::new(Ty::Instance(class))));
+        }
+        //running off the end is the same as a bare return
+        if let Some(returns) = returns
+            && !returns.value
+            && (returns.null || can_complete(&declaration.body))
+        {
+            self.line = declaration.line;
+            self.unify(&ret, &Ty::Null)?;
+        }
+        Ok(Ty::Function(params, Box::new(ret)))
+    }
+
+    fn infer_class(
+        &mut self,
+        name: &str,
+        superclass: &Option<Expr>,
+        methods: &Vec<Rc<FunctionDecl>>,
+    ) -> Result<(), CheckError> {
+        let superclass = match superclass {
+            Some(Expr::Variable { name, .. }) => Some(name.to_string()),
+            Some(superclass) => {
+                self.infer_expr(superclass)?;
+                None
+            }
+            None => None,
+        };
+        //every method gets a var up front so methods can call each other in any order
+        let mut types: ClassTypes = ClassTypes {
+            superclass,
+            ..ClassTypes::default()
+        };
+        for method in methods {
+            let ty: Ty = match method.name == "init" {
+                //init always hands back the instance
+                true => Ty::Function(
+                    method.params.iter().map(|_| self.fresh()).collect(),
+                    Box::new(Ty::Instance(name.to_string())),
+                ),
+                false => self.fresh(),
+            };
+            types.methods.insert(method.name.to_string(), ty);
+        }
+        self.classes.insert(name.to_string(), types);
+        self.define(name, Scheme::mono(Ty::Class(name.to_string())));
+
+        let enclosing_class = self.current_class.replace(name.to_string());
+        let mut result = Ok(());
+        for method in methods {
+            result = self
+                .infer_function(method, method.name == "init")
+                .and_then(|ty| {
+                    let declared: Ty = self.classes[name].methods[&method.name].clone();
+                    self.unify(&declared, &ty)
+                });
+            if result.is_err() {
+                break;
+            }
+            let ty: Ty = self.classes[name].methods[&method.name].clone();
+            self.signatures
+                .push((format!("{}.{}", name, method.name), Scheme::mono(ty)));
+        }
+        self.current_class = enclosing_class;
+        result
+    }
+
+    fn bind_pattern(&mut self, pattern: &Pattern, subject: &Ty) -> Result<(), CheckError> {
+        match pattern {
+            Pattern::Wildcard => Ok(()),
+            Pattern::Binding(name) => {
+                self.define(name, Scheme::mono(subject.clone()));
+                Ok(())
+            }
+            Pattern::Literal(literal) => {
+                let ty: Ty = self.literal_type(literal);
+                self.unify(subject, &ty)
+            }
+            Pattern::Range { .. } => self.unify(subject, &Ty::Number),
+            Pattern::List { elements, rest } => {
+                let element: Ty = self.fresh();
+                let list: Ty = Ty::List(Box::new(element.clone()));
+                self.unify(subject, &list)?;
+                for pattern in elements {
+                    self.bind_pattern(pattern, &element)?;
+                }
+                match rest {
+                    Some(rest) => self.bind_pattern(rest, &list),
+                    None => Ok(()),
+                }
+            }
+            Pattern::Map(entries) => {
+                let key: Ty = self.fresh();
+                let value: Ty = self.fresh();
+                self.unify(
+                    subject,
+                    &Ty::Map(Box::new(key.clone()), Box::new(value.clone())),
+                )?;
+                for (literal, pattern) in entries {
+                    let ty: Ty = self.literal_type(literal);
+                    self.unify(&key, &ty)?;
+                    self.bind_pattern(pattern, &value)?;
+                }
+                Ok(())
+            }
+            Pattern::Instance {
+                class,
+                fields,
+                line,
+            } => {
+                self.line = *line;
+                let class = match self.infer_expr(class)? {
+                    Ty::Class(class) => class,
+                    _ => {
+                        for (_, pattern) in fields {
+                            self.bind_pattern(pattern, &Ty::Any)?;
+                        }
+                        return Ok(());
+                    }
+                };
+                self.unify(subject, &Ty::Instance(class.to_string()))?;
+                for (field, pattern) in fields {
+                    let ty: Ty = self.property_type(&class, field);
+                    self.bind_pattern(pattern, &ty)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn infer_match_arm(&mut self, arm: &MatchArm, subject: &Ty) -> Result<Ty, CheckError> {
+        self.begin_scope();
+        let mut result = self.bind_pattern(&arm.pattern, subject);
+        if result.is_ok()
+            && let Some(guard) = &arm.guard
+        {
+            result = self.infer_expr(guard).map(|_| ());
+        }
+        let result = result.and_then(|_| self.infer_expr(&arm.body));
+        self.end_scope();
+        result
+    }
+
+    fn infer_statements(&mut self, statements: &Vec<Stmt>) -> Result<(), CheckError> {
+        for statement in statements {
+            self.infer_stmt(statement)?;
+        }
+        Ok(())
+    }
+
+    fn infer_stmt_block(&mut self, statements: &Vec<Stmt>) -> Result<(), CheckError> {
+        self.begin_scope();
+        let result = self.infer_statements(statements);
+        self.end_scope();
+        result
+    }
+
+    fn infer_stmt(&mut self, stmt: &Stmt) -> Result<(), CheckError> {
+        match stmt {
+            Stmt::Print(e) | Stmt::Expression(e) | Stmt::Throw { value: e, .. } => {
+                self.infer_expr(e).map(|_| ())
+            }
+            Stmt::Var {
+                name,
+                initializer,
+                mutable,
+                annotation,
+                line,
+            } => {
+                self.line = *line;
+                let ty: Ty = match initializer {
+                    Some(initializer) => self.infer_expr(initializer)?,
+                    None => self.fresh(),
+                };
+                self.line = *line;
+                //the binding takes the annotated type, a subclass instance is held as its superclass
+                let ty: Ty = match annotation {
+                    Some(annotation) => {
+                        let expected: Ty = self.annotation_type(annotation);
+                        self.unify(&expected, &ty)?;
+                        expected
+                    }
+                    None => ty,
+                };
+                //only bindings that can never be reassigned are safe to generalize
+                let scheme: Scheme = match mutable {
+                    true => Scheme::mono(ty),
+                    false => self.generalize(&ty),
+                };
+                if self.scopes.len() == 1 {
+                    self.signatures.push((name.to_string(), scheme.clone()));
+                }
+                self.define(name, scheme);
+                Ok(())
+            }
+            Stmt::Block(statements) => self.infer_stmt_block(statements),
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.infer_expr(condition)?;
+                self.infer_stmt(then_branch)?;
+                match else_branch {
+                    Some(else_branch) => self.infer_stmt(else_branch),
+                    None => Ok(()),
+                }
+            }
+            Stmt::While {
+                condition,
+                body,
+                increment,
+                ..
+            } => {
+                self.infer_expr(condition)?;
+                self.infer_stmt(body)?;
+                match increment {
+                    Some(increment) => self.infer_expr(increment).map(|_| ()),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Break { .. } | Stmt::Continue { .. } => Ok(()),
+            Stmt::Function(declaration) => {
+                //monomorphic inside its own body, generalized once the body is inferred
+                let own: Ty = self.fresh();
+                self.define(&declaration.name, Scheme::mono(own.clone()));
+                let ty: Ty = self.infer_function(declaration, false)?;
+                self.unify(&own, &ty)?;
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.remove(&declaration.name);
+                }
+                let scheme: Scheme = self.generalize(&ty);
+                if self.scopes.len() == 1 {
+                    self.signatures
+                        .push((declaration.name.to_string(), scheme.clone()));
+                }
+                self.define(&declaration.name, scheme);
+                Ok(())
+            }
+            Stmt::Return { value, line } => {
+                let ty: Ty = match value {
+                    Some(value) => self.infer_expr(value)?,
+                    None => self.fresh(),
+                };
+                self.line = *line;
+                let null: bool = matches!(value, None | Some(Expr::Literal(Literal::Null)));
+                match self.returns.last_mut() {
+                    Some(returns) => {
+                        returns.value |= !null;
+                        returns.null |= null;
+                        let expected: Ty = returns.ty.clone();
+                        self.unify(&expected, &ty)
+                    }
+                    None => Ok(()),
+                }
+            }
+            //modules are loaded at runtime and aren't inferred, so what they export is dynamic
+            Stmt::Import { alias, names, .. } => {
+                if let Some(alias) = alias {
+                    self.define(alias, Scheme::mono(Ty::Module));
+                }
+                for name in names {
+                    self.define(name, Scheme::mono(Ty::Any));
+                }
+                Ok(())
+            }
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                self.infer_stmt_block(body)?;
+                if let Some((name, catch_body)) = catch {
+                    //anything can be thrown
+                    self.begin_scope();
+                    self.define(name, Scheme::mono(Ty::Any));
+                    let result = self.infer_statements(catch_body);
+                    self.end_scope();
+                    result?;
+                }
+                match finally {
+                    Some(finally) => self.infer_stmt_block(finally),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Class {
+                name,
+                superclass,
+                methods,
+                line,
+            } => {
+                self.line = *line;
+                self.infer_class(name, superclass, methods)
+            }
+        }
+    }
+
+    fn infer_expr(&mut self, expr: &Expr) -> Result<Ty, CheckError> {
+        match expr {
+            Expr::Binary {
+                left,
+                op,
+                right,
+                line,
+            } => {
+                let left: Ty = self.infer_expr(left)?;
+                let right: Ty = self.infer_expr(right)?;
+                self.line = *line;
+                self.binary_type(op, &left, &right)
+            }
+            Expr::Logical { left, op, right } => {
+                let left: Ty = self.infer_expr(left)?;
+                let right: Ty = self.infer_expr(right)?;
+                self.binary_type(op, &left, &right)
+            }
+            Expr::Match {
+                subject,
+                arms,
+                line,
+            } => {
+                let subject: Ty = self.infer_expr(subject)?;
+                let result: Ty = self.fresh();
+                for arm in arms {
+                    let ty: Ty = self.infer_match_arm(arm, &subject)?;
+                    self.line = *line;
+                    self.unify(&result, &ty)?;
+                }
+                Ok(result)
+            }
+            Expr::Lambda(declaration) => self.infer_function(declaration, false),
+            Expr::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.infer_expr(condition)?;
+                let then_type: Ty = self.infer_expr(then_branch)?;
+                let else_type: Ty = self.infer_expr(else_branch)?;
+                self.unify(&then_type, &else_type)?;
+                Ok(then_type)
+            }
+            Expr::Unary { op, right, line } => {
+                let right: Ty = self.infer_expr(right)?;
+                self.line = *line;
+                match op {
+                    UnaryOp::Bang => Ok(Ty::Boolean),
+                    _ => {
+                        self.unify(&Ty::Number, &right)?;
+                        Ok(Ty::Number)
+                    }
+                }
+            }
+            Expr::Grouping { exp } => self.infer_expr(exp),
+            Expr::Literal(literal) => Ok(self.literal_type(literal)),
+            Expr::Variable { name, line, .. } => {
+                self.line = *line;
+                Ok(self.lookup(name))
+            }
+            Expr::Assign {
+                name, value, line, ..
+            } => {
+                let ty: Ty = self.infer_expr(value)?;
+                self.line = *line;
+                let current: Ty = self.lookup(name);
+                self.unify(&current, &ty)?;
+                Ok(ty)
+            }
+            Expr::Call {
+                callee,
+                arguments,
+                line,
+            } => {
+                let callee: Ty = self.infer_expr(callee)?;
+                let mut found: Vec<Ty> = Vec::new();
+                for argument in arguments {
+                    found.push(self.infer_expr(argument)?);
+                }
+                self.line = *line;
+                match self.resolve(&callee) {
+                    Ty::Any => Ok(Ty::Any),
+                    Ty::Class(name) => {
+                        let init: Ty = match self.find_method(&name, "init") {
+                            Some(init) => init,
+                            None => Ty::Function(Vec::new(), Box::new(self.fresh())),
+                        };
+                        let ret: Ty = self.fresh();
+                        self.unify(&init, &Ty::Function(found, Box::new(ret)))?;
+                        Ok(Ty::Instance(name))
+                    }
+                    Ty::Function(..) | Ty::Var(_) => {
+                        let ret: Ty = self.fresh();
+                        self.unify(&callee, &Ty::Function(found, Box::new(ret.clone())))?;
+                        Ok(ret)
+                    }
+                    callee => Err(CheckError::NotCallable {
+                        found: self.display(&callee),
+                        line: *line,
+                    }),
+                }
+            }
+            Expr::Get { object, name, line } => {
+                let object: Ty = self.infer_expr(object)?;
+                self.line = *line;
+                match self.resolve(&object) {
+                    Ty::Instance(class) => Ok(self.property_type(&class, name)),
+                    _ => Ok(Ty::Any),
+                }
+            }
+            Expr::Set {
+                object,
+                name,
+                value,
+                line,
+            } => {
+                let ty: Ty = self.infer_expr(value)?;
+                let object: Ty = self.infer_expr(object)?;
+                self.line = *line;
+                if let Ty::Instance(class) = self.resolve(&object) {
+                    let field: Ty = self.property_type(&class, name);
+                    self.unify(&field, &ty)?;
+                }
+                Ok(ty)
+            }
+            Expr::This { line, .. } => {
+                self.line = *line;
+                Ok(match &self.current_class {
+                    Some(class) => Ty::Instance(class.to_string()),
+                    None => Ty::Any,
+                })
+            }
+            Expr::Super { line, .. } => {
+                self.line = *line;
+                Ok(Ty::Any)
+            }
+            Expr::Interpolation(parts) => {
+                for part in parts {
+                    self.infer_expr(part)?;
+                }
+                Ok(Ty::String)
+            }
+            Expr::List(elements) => {
+                let element: Ty = self.fresh();
+                for value in elements {
+                    let ty: Ty = self.infer_expr(value)?;
+                    self.unify(&element, &ty)?;
+                }
+                Ok(Ty::List(Box::new(element)))
+            }
+            Expr::Map { entries, line } => {
+                let key: Ty = self.fresh();
+                let value: Ty = self.fresh();
+                for (k, v) in entries {
+                    let k: Ty = self.infer_expr(k)?;
+                    let v: Ty = self.infer_expr(v)?;
+                    self.line = *line;
+                    self.unify(&key, &k)?;
+                    self.unify(&value, &v)?;
+                }
+                Ok(Ty::Map(Box::new(key), Box::new(value)))
+            }
+            Expr::Index {
+                object,
+                index,
+                line,
+            } => {
+                let object: Ty = self.infer_expr(object)?;
+                let index: Ty = self.infer_expr(index)?;
+                self.line = *line;
+                self.index_type(&object, &index)
+            }
+            Expr::IndexSet {
+                object,
+                index,
+                value,
+                line,
+            } => {
+                let object: Ty = self.infer_expr(object)?;
+                let index: Ty = self.infer_expr(index)?;
+                let ty: Ty = self.infer_expr(value)?;
+                self.line = *line;
+                let element: Ty = self.index_type(&object, &index)?;
+                self.unify(&element, &ty)?;
+                Ok(ty)
+            }
+            Expr::CompoundAssign {
+                target,
+                op,
+                value,
+                line,
+            } => {
+                let current: Ty = self.infer_expr(target)?;
+                let ty: Ty = self.infer_expr(value)?;
+                self.line = *line;
+                let result: Ty = self.binary_type(op, &current, &ty)?;
+                self.unify(&current, &result)?;
+                Ok(result)
+            }
+            Expr::Increment { target, line, .. } => {
+                let current: Ty = self.infer_expr(target)?;
+                self.line = *line;
+                self.unify(&Ty::Number, &current)?;
+                Ok(Ty::Number)
+            }
+        }
+    }
+}
+
+//opt in with --typed. infers a type for every binding, generalizing functions and constants, and
+//returns the top level signatures. stricter than check in that lists and maps have to hold a
+//single type, while a + b on unknown operands stays open until one side is known
+pub fn infer(statements: &Vec<Stmt>) -> Result<Vec<(String, String)>, CheckError> {
+    let mut inferrer: Inferrer = Inferrer::new();
+    inferrer.infer_statements(statements)?;
+    inferrer.finish_pending(0)?;
+    Ok(inferrer
+        .signatures
+        .iter()
+        .map(|(name, scheme)| (name.to_string(), inferrer.display(&scheme.ty)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexing::lexer::lex_program, parsing::parser::parse_tokens};
+
+    fn infer_source(source: &str) -> Result<Vec<(String, String)>, CheckError> {
+        let tokens = lex_program(source).expect("test source lexes");
+        let statements = parse_tokens(&tokens).expect("test source parses");
+        infer(&statements)
+    }
+
+    //the signature printed for a top level name
+    fn signature(source: &str, name: &str) -> String {
+        infer_source(source)
+            .expect("test source infers")
+            .into_iter()
+            .find(|(found, _)| found == name)
+            .map(|(_, signature)| signature)
+            .expect("name is bound at the top level")
+    }
+
+    fn rejection(source: &str) -> String {
+        infer_source(source)
+            .expect_err("test source is rejected")
+            .to_string()
+    }
+
+    #[test]
+    fn functions_are_generalized_at_each_use() {
+        let source = "fun id(x) { return x; }\nvar a = id(1);\nvar b = id(\"s\");";
+        assert_eq!(signature(source, "id"), "fun('a) -> 'a");
+        assert_eq!(signature(source, "a"), "number");
+        assert_eq!(signature(source, "b"), "string");
+    }
+
+    #[test]
+    fn params_stay_monomorphic_inside_the_body() {
+        let source = "fun apply(f) { f(1);\nreturn f(\"s\"); }";
+        assert_eq!(
+            rejection(source),
+            "type mismatch: expected number but found string at line 2"
+        );
+    }
+
+    #[test]
+    fn plus_on_unknown_operands_is_deferred() {
+        let source =
+            "fun join(a, b) { return a + b; }\nvar s = join(\"a\", \"b\");\nvar n = join(1, 2);";
+        assert_eq!(signature(source, "join"), "fun('a, 'b) -> any");
+        assert_eq!(signature(source, "s"), "any");
+    }
+
+    #[test]
+    fn plus_settles_once_an_operand_is_known() {
+        let source = "fun inc(n) { return n + 1; }\nfun shout(s) { var t = s + \"!\"; return t; }";
+        assert_eq!(signature(source, "inc"), "fun(number) -> number");
+        assert_eq!(signature(source, "shout"), "fun(string) -> string");
+        assert_eq!(
+            rejection("fun inc(n) { return n + 1; }\ninc(\"a\");"),
+            "type mismatch: expected number but found string at line 2"
+        );
+    }
+
+    #[test]
+    fn lists_are_indexed_by_numbers() {
+        let source = "var xs = [1, 2];\nvar head = xs[0];";
+        assert_eq!(signature(source, "head"), "number");
+        assert_eq!(
+            rejection("var xs = [1, 2];\nxs[\"a\"];"),
+            "type mismatch: expected number but found string at line 2"
+        );
+    }
+
+    #[test]
+    fn maps_are_indexed_by_their_key_type() {
+        let source = "var m = {\"a\": 1};\nvar v = m[\"a\"];";
+        assert_eq!(signature(source, "v"), "number");
+        assert_eq!(
+            rejection("var m = {\"a\": 1};\nm[1];"),
+            "type mismatch: expected string but found number at line 2"
+        );
+    }
+
+    #[test]
+    fn indexing_an_unknown_operand_is_deferred() {
+        let source =
+            "fun get(m, k) { return m[k]; }\nvar v = get({\"a\": 1}, \"a\");\nvar w = get([1], 0);";
+        assert_eq!(signature(source, "get"), "fun('a, 'b) -> any");
+        let source = "fun first(xs) { var n = xs[0]; return n + 1; }";
+        assert_eq!(signature(source, "first"), "fun('a) -> number");
+    }
+
+    #[test]
+    fn falling_through_is_a_null_return() {
+        let implicit = "fun f(n) { if (n > 0) return 1; }";
+        let explicit = "fun f(n) { if (n > 0) return 1; return null; }";
+        assert_eq!(signature(implicit, "f"), "fun(number) -> number");
+        assert_eq!(signature(explicit, "f"), "fun(number) -> number");
+        assert_eq!(
+            signature("fun early(n) { if (n > 1) { return; } print n; }", "early"),
+            "fun(number) -> null"
+        );
+        assert_eq!(
+            signature("fun fail() { throw \"x\"; }", "fail"),
+            "fun() -> 'a"
+        );
+        assert_eq!(
+            rejection("fun noret() { print 1; }\nvar y: int = noret();"),
+            "type mismatch: expected number but found null at line 2"
+        );
+    }
+
+    #[test]
+    fn subclass_instances_unify_with_their_superclass() {
+        let source = "class A { name() { return \"a\"; } }\nclass B < A { }\n\
+            fun show(x: A) { return x.name(); }\nvar s = show(B());\nvar item: A = B();\n\
+            fun pick(flag) { if (flag) return A(); return B(); }";
+        assert_eq!(signature(source, "s"), "string");
+        assert_eq!(signature(source, "item"), "A");
+        assert_eq!(signature(source, "pick"), "fun('a) -> A");
+        assert_eq!(
+            rejection("class A { }\nclass C { }\nfun show(x: A) { return x; }\nshow(C());"),
+            "type mismatch: expected A but found C at line 4"
+        );
+    }
+
+    #[test]
+    fn error_is_built_from_a_message() {
+        let source = "var e = Error(\"boom\");\nvar m = e.line;";
+        assert_eq!(signature(source, "e"), "Error");
+        assert_eq!(signature(source, "m"), "number");
+    }
+}
@@ -1,33 +1,128 @@
-use std::collections::HashMap;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use crate::{errors::environment_error::EnvironmentError, interpreting::value::Value};
 
 pub(crate) struct Environment {
     values: HashMap<String, Value>,
+    //names in values that were declared with const or let
+    constants: HashSet<String>,
+    enclosing: Option<Rc<RefCell<Environment>>>,
 }
 
 impl Environment {
     pub fn new() -> Self {
         Environment {
             values: HashMap::new(),
+            constants: HashSet::new(),
+            enclosing: None,
+        }
+    }
+
+    pub fn new_enclosed(enclosing: Rc<RefCell<Environment>>) -> Self {
+        Environment {
+            values: HashMap::new(),
+            constants: HashSet::new(),
+            enclosing: Some(enclosing),
         }
     }
 
     //current decision is to error when redefining a variable with var keyword
+    //only checks the current scope so inner blocks are free to shadow outer names
     pub fn define(&mut self, name: String, val: Value) -> Result<(), EnvironmentError> {
         match self.values.get(&name) {
             None => {
                 self.values.insert(name, val);
-                return Ok(());
+                Ok(())
             }
             Some(_) => Err(EnvironmentError::MultipleAssignmentVariable(name)),
         }
     }
 
+    pub fn define_constant(&mut self, name: String, val: Value) -> Result<(), EnvironmentError> {
+        self.define(name.to_string(), val)?;
+        self.constants.insert(name);
+        Ok(())
+    }
+
+    //only looks in this scope, a module's exports all live in its outermost one
+    pub fn is_constant(&self, name: &str) -> bool {
+        self.constants.contains(name)
+    }
+
+    //every assignment funnels through here once the owning scope is found
+    fn assign_here(&mut self, name: String, val: Value) -> Result<(), EnvironmentError> {
+        if self.constants.contains(&name) {
+            return Err(EnvironmentError::AssignToConstant(name));
+        }
+        match self.values.get_mut(&name) {
+            Some(v) => {
+                *v = val;
+                Ok(())
+            }
+            None => Err(EnvironmentError::UndefinedVariable(name)),
+        }
+    }
+
     pub fn get(&mut self, name: String) -> Result<Value, EnvironmentError> {
         match self.values.get(&name) {
             Some(v) => Ok(v.clone()),
+            None => match &self.enclosing {
+                Some(enclosing) => enclosing.borrow_mut().get(name),
+                None => Err(EnvironmentError::UndefinedVariable(name)),
+            },
+        }
+    }
+
+    //distance comes from the resolver, so the binding is read from exactly that many scopes out
+    pub fn get_at(&mut self, distance: usize, name: String) -> Result<Value, EnvironmentError> {
+        if distance == 0 {
+            return match self.values.get(&name) {
+                Some(v) => Ok(v.clone()),
+                None => Err(EnvironmentError::UndefinedVariable(name)),
+            };
+        }
+        match &self.enclosing {
+            Some(enclosing) => enclosing.borrow_mut().get_at(distance - 1, name),
             None => Err(EnvironmentError::UndefinedVariable(name)),
         }
     }
+
+    pub fn assign_at(
+        &mut self,
+        distance: usize,
+        name: String,
+        val: Value,
+    ) -> Result<(), EnvironmentError> {
+        if distance == 0 {
+            return self.assign_here(name, val);
+        }
+        match &self.enclosing {
+            Some(enclosing) => enclosing.borrow_mut().assign_at(distance - 1, name, val),
+            None => Err(EnvironmentError::UndefinedVariable(name)),
+        }
+    }
+
+    //the outermost scope of a chain is the global scope of the file the chain was created in, so
+    //functions imported from a module keep seeing that module's globals
+    pub fn get_global(&mut self, name: String) -> Result<Value, EnvironmentError> {
+        match &self.enclosing {
+            Some(enclosing) => enclosing.borrow_mut().get_global(name),
+            None => match self.values.get(&name) {
+                Some(v) => Ok(v.clone()),
+                None => Err(EnvironmentError::UndefinedVariable(name)),
+            },
+        }
+    }
+
+    //assignment only updates an existing binding, it never implicitly declares one
+    pub fn assign_global(&mut self, name: String, val: Value) -> Result<(), EnvironmentError> {
+        match &self.enclosing {
+            Some(enclosing) => enclosing.borrow_mut().assign_global(name, val),
+            None => self.assign_here(name, val),
+        }
+    }
 }
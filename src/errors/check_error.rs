@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::fmt;
+
+//types are carried as their display names so the checker's own type enum stays private
+#[derive(Debug)]
+pub enum CheckError {
+    TypeMismatch {
+        expected: String,
+        found: String,
+        line: usize,
+    },
+    InvalidOperands {
+        op: String,
+        left: String,
+        right: String,
+        line: usize,
+    },
+    InvalidOperand {
+        op: String,
+        operand: String,
+        line: usize,
+    },
+    InvalidArgumentCount {
+        expected: usize,
+        found: usize,
+        line: usize,
+    },
+    NotCallable {
+        found: String,
+        line: usize,
+    },
+    NotIndexable {
+        found: String,
+        line: usize,
+    },
+    UnknownType {
+        name: String,
+        line: usize,
+    },
+    RecursiveType {
+        var: String,
+        kind: String,
+        line: usize,
+    },
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckError::TypeMismatch {
+                expected,
+                found,
+                line,
+            } => write!(
+                f,
+                "type mismatch: expected {} but found {} at line {}",
+                expected, found, line
+            ),
+            CheckError::InvalidOperands {
+                op,
+                left,
+                right,
+                line,
+            } => write!(
+                f,
+                "invalid operands for {}: {} and {} at line {}",
+                op, left, right, line
+            ),
+            CheckError::InvalidOperand { op, operand, line } => {
+                write!(
+                    f,
+                    "invalid operand for {}: {} at line {}",
+                    op, operand, line
+                )
+            }
+            CheckError::InvalidArgumentCount {
+                expected,
+                found,
+                line,
+            } => write!(
+                f,
+                "expected {} arguments but found {} at line {}",
+                expected, found, line
+            ),
+            CheckError::NotCallable { found, line } => {
+                write!(f, "cannot call a value of type {} at line {}", found, line)
+            }
+            CheckError::NotIndexable { found, line } => {
+                write!(f, "cannot index a value of type {} at line {}", found, line)
+            }
+            CheckError::UnknownType { name, line } => {
+                write!(f, "unknown type {} at line {}", name, line)
+            }
+            CheckError::RecursiveType { var, kind, line } => write!(
+                f,
+                "recursive type: {} occurs in {} at line {}",
+                var, kind, line
+            ),
+        }
+    }
+}
+
+impl Error for CheckError {}
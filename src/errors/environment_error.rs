@@ -1,10 +1,10 @@
 use std::error::Error;
-use std::fmt;
 
 #[derive(Debug)]
 pub enum EnvironmentError {
     MultipleAssignmentVariable(String),
     UndefinedVariable(String),
+    AssignToConstant(String),
 }
 
 impl std::fmt::Display for EnvironmentError {
@@ -20,6 +20,9 @@ impl std::fmt::Display for EnvironmentError {
             EnvironmentError::UndefinedVariable(name) => {
                 write!(f, "undefined variable: {}", name)
             }
+            EnvironmentError::AssignToConstant(name) => {
+                write!(f, "cannot assign to constant: {}", name)
+            }
         }
     }
 }
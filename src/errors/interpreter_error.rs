@@ -1,14 +1,19 @@
 use std::error::Error;
 use std::fmt;
 
+use crate::errors::check_error::CheckError;
 use crate::errors::lex_error::LexError;
 use crate::errors::parse_error::ParseError;
+use crate::errors::resolve_error::ResolveError;
 use crate::errors::runtime_error::RunTimeError;
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum InterpreterError {
     LexError(LexError),
     ParseError(ParseError),
+    ResolveError(ResolveError),
+    CheckError(CheckError),
     RunTimeError(RunTimeError),
 }
 
@@ -17,6 +22,8 @@ impl std::fmt::Display for InterpreterError {
         match self {
             InterpreterError::LexError(e) => write!(f, "{}", e),
             InterpreterError::ParseError(e) => write!(f, "{}", e),
+            InterpreterError::ResolveError(e) => write!(f, "{}", e),
+            InterpreterError::CheckError(e) => write!(f, "{}", e),
             InterpreterError::RunTimeError(e) => write!(f, "{}", e),
         }
     }
@@ -27,6 +34,8 @@ impl Error for InterpreterError {
         match self {
             InterpreterError::LexError(e) => Some(e),
             InterpreterError::ParseError(e) => Some(e),
+            InterpreterError::ResolveError(e) => Some(e),
+            InterpreterError::CheckError(e) => Some(e),
             InterpreterError::RunTimeError(e) => Some(e),
         }
     }
@@ -44,6 +53,18 @@ impl From<ParseError> for InterpreterError {
     }
 }
 
+impl From<ResolveError> for InterpreterError {
+    fn from(value: ResolveError) -> Self {
+        InterpreterError::ResolveError(value)
+    }
+}
+
+impl From<CheckError> for InterpreterError {
+    fn from(value: CheckError) -> Self {
+        InterpreterError::CheckError(value)
+    }
+}
+
 impl From<RunTimeError> for InterpreterError {
     fn from(value: RunTimeError) -> Self {
         InterpreterError::RunTimeError(value)
@@ -7,6 +7,7 @@ pub enum LexError {
     UnexpectedCharacter { char: char, line: usize },
     UnterminatedString { line: usize },
     NumberParsingError { num_str: String, line: usize },
+    InvalidEscape { sequence: String, line: usize },
 }
 
 impl fmt::Display for LexError {
@@ -21,7 +22,10 @@ impl fmt::Display for LexError {
                 write!(f, "unterminated string at line: {}", line)
             }
             LexError::NumberParsingError { num_str, line } => {
-                write!(f, "failed to parse double: {} on line {}", num_str, line)
+                write!(f, "failed to parse number: {} on line {}", num_str, line)
+            }
+            LexError::InvalidEscape { sequence, line } => {
+                write!(f, "invalid escape sequence {} on line {}", sequence, line)
             }
         }
     }
@@ -1,5 +1,7 @@
+pub mod check_error;
 pub mod environment_error;
 pub mod interpreter_error;
 pub mod lex_error;
 pub mod parse_error;
+pub mod resolve_error;
 pub mod runtime_error;
@@ -1,8 +1,6 @@
 use std::error::Error;
 use std::fmt;
 
-use crate::parsing::ast::Expr;
-
 #[derive(Debug)]
 pub enum ParseError {
     InvalidConversion(String),
@@ -10,6 +8,11 @@ pub enum ParseError {
     InvalidGrouping(String),
     MissingValue { val: String, line: usize },
     InvalidDeclaration(String),
+    InvalidAssignmentTarget { line: usize },
+    InvalidLoopControl { keyword: String, line: usize },
+    UndefinedLabel { label: String, line: usize },
+    InvalidPattern { line: usize },
+    UninitializedConstant { name: String, line: usize },
 }
 
 impl std::fmt::Display for ParseError {
@@ -26,6 +29,21 @@ impl std::fmt::Display for ParseError {
             ParseError::InvalidDeclaration(e) => {
                 write!(f, "Invalid declaration: {}", e)
             }
+            ParseError::InvalidAssignmentTarget { line } => {
+                write!(f, "Invalid assignment target at line {}", line)
+            }
+            ParseError::InvalidLoopControl { keyword, line } => {
+                write!(f, "{} used outside of a loop at line {}", keyword, line)
+            }
+            ParseError::UndefinedLabel { label, line } => {
+                write!(f, "no enclosing loop labeled {} at line {}", label, line)
+            }
+            ParseError::InvalidPattern { line } => {
+                write!(f, "Invalid match pattern at line {}", line)
+            }
+            ParseError::UninitializedConstant { name, line } => {
+                write!(f, "Constant {} must be initialized at line {}", name, line)
+            }
         }
     }
 }
@@ -0,0 +1,59 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ResolveError {
+    ReadInOwnInitializer { name: String, line: usize },
+    TopLevelReturn { line: usize },
+    ReturnFromInitializer { line: usize },
+    ThisOutsideClass { line: usize },
+    SuperOutsideClass { line: usize },
+    SuperWithoutSuperclass { line: usize },
+    SelfInheritance { name: String, line: usize },
+    AssignToConstant { name: String, line: usize },
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResolveError::ReadInOwnInitializer { name, line } => write!(
+                f,
+                "cannot read local variable {} in its own initializer at line {}",
+                name, line
+            ),
+            ResolveError::TopLevelReturn { line } => {
+                write!(f, "cannot return from top-level code at line {}", line)
+            }
+            ResolveError::ReturnFromInitializer { line } => {
+                write!(
+                    f,
+                    "cannot return a value from an initializer at line {}",
+                    line
+                )
+            }
+            ResolveError::ThisOutsideClass { line } => {
+                write!(f, "cannot use this outside of a class at line {}", line)
+            }
+            ResolveError::SuperOutsideClass { line } => {
+                write!(f, "cannot use super outside of a class at line {}", line)
+            }
+            ResolveError::SuperWithoutSuperclass { line } => write!(
+                f,
+                "cannot use super in a class with no superclass at line {}",
+                line
+            ),
+            ResolveError::SelfInheritance { name, line } => {
+                write!(
+                    f,
+                    "class {} cannot inherit from itself at line {}",
+                    name, line
+                )
+            }
+            ResolveError::AssignToConstant { name, line } => {
+                write!(f, "cannot assign to constant {} at line {}", name, line)
+            }
+        }
+    }
+}
+
+impl Error for ResolveError {}
@@ -2,25 +2,224 @@ use std::error::Error;
 use std::fmt;
 
 use crate::errors::environment_error::EnvironmentError;
+use crate::interpreting::value::Value;
 #[derive(Debug)]
 pub enum RunTimeError {
     CouldNotEval(String),
-    EnvironmentError(EnvironmentError),
+    EnvironmentError {
+        error: EnvironmentError,
+        line: usize,
+    },
+    NotCallable {
+        line: usize,
+    },
+    InvalidArgumentCount {
+        expected: usize,
+        found: usize,
+        line: usize,
+    },
+    UndefinedProperty {
+        name: String,
+        line: usize,
+    },
+    InvalidPropertyAccess {
+        line: usize,
+    },
+    InvalidSuperclass {
+        line: usize,
+    },
+    IndexOutOfBounds {
+        index: i64,
+        length: usize,
+        line: usize,
+    },
+    InvalidIndex {
+        line: usize,
+    },
+    NotIndexable {
+        line: usize,
+    },
+    InvalidKey {
+        line: usize,
+    },
+    UndefinedKey {
+        key: String,
+        line: usize,
+    },
+    IntegerOverflow {
+        op: String,
+        line: usize,
+    },
+    DivisionByZero {
+        line: usize,
+    },
+    InvalidShift {
+        amount: i64,
+        line: usize,
+    },
+    NoMatchingArm {
+        value: String,
+        line: usize,
+    },
+    InvalidPatternClass {
+        line: usize,
+    },
+    ModuleNotFound {
+        path: String,
+        line: usize,
+    },
+    ImportCycle {
+        path: String,
+        line: usize,
+    },
+    //the imported file failed to lex, parse or resolve
+    InvalidModule {
+        path: String,
+        error: String,
+        line: usize,
+    },
+    //a thrown value that no catch handled
+    Thrown {
+        value: Value,
+        line: usize,
+    },
+    //not a real error, unwinds the call stack back to the enclosing function call
+    Return(Value),
+    //same idea as return but unwinds back to the matching loop
+    Break(Option<String>),
+    Continue(Option<String>),
 }
 
 impl fmt::Display for RunTimeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             RunTimeError::CouldNotEval(val) => write!(f, "Could not evaluate: {}", val),
-            RunTimeError::EnvironmentError(err) => write!(f, "env error: {}", err),
+            RunTimeError::EnvironmentError { error, line } => {
+                write!(f, "env error: {} at line {}", error, line)
+            }
+            RunTimeError::NotCallable { line } => {
+                write!(f, "can only call functions and classes at line {}", line)
+            }
+            RunTimeError::InvalidArgumentCount {
+                expected,
+                found,
+                line,
+            } => write!(
+                f,
+                "expected {} arguments but got {} at line {}",
+                expected, found, line
+            ),
+            RunTimeError::UndefinedProperty { name, line } => {
+                write!(f, "undefined property {} at line {}", name, line)
+            }
+            RunTimeError::InvalidPropertyAccess { line } => {
+                write!(f, "only instances have properties at line {}", line)
+            }
+            RunTimeError::InvalidSuperclass { line } => {
+                write!(f, "superclass must be a class at line {}", line)
+            }
+            RunTimeError::IndexOutOfBounds {
+                index,
+                length,
+                line,
+            } => write!(
+                f,
+                "index {} out of bounds for list of length {} at line {}",
+                index, length, line
+            ),
+            RunTimeError::InvalidIndex { line } => {
+                write!(f, "lists can only be indexed by integers at line {}", line)
+            }
+            RunTimeError::NotIndexable { line } => {
+                write!(f, "only lists and maps can be indexed at line {}", line)
+            }
+            RunTimeError::InvalidKey { line } => write!(
+                f,
+                "map keys must be strings, numbers or booleans at line {}",
+                line
+            ),
+            RunTimeError::UndefinedKey { key, line } => {
+                write!(f, "undefined key {} at line {}", key, line)
+            }
+            RunTimeError::IntegerOverflow { op, line } => {
+                write!(f, "integer overflow evaluating {} at line {}", op, line)
+            }
+            RunTimeError::DivisionByZero { line } => {
+                write!(f, "integer division by zero at line {}", line)
+            }
+            RunTimeError::InvalidShift { amount, line } => write!(
+                f,
+                "shift amount {} must be between 0 and 63 at line {}",
+                amount, line
+            ),
+            RunTimeError::NoMatchingArm { value, line } => {
+                write!(f, "no match arm matched {} at line {}", value, line)
+            }
+            RunTimeError::InvalidPatternClass { line } => {
+                write!(f, "instance pattern must name a class at line {}", line)
+            }
+            RunTimeError::ModuleNotFound { path, line } => {
+                write!(f, "could not find module {} at line {}", path, line)
+            }
+            RunTimeError::ImportCycle { path, line } => {
+                write!(f, "import cycle through {} at line {}", path, line)
+            }
+            RunTimeError::InvalidModule { path, error, line } => {
+                write!(
+                    f,
+                    "error in module {} imported at line {}: {}",
+                    path, line, error
+                )
+            }
+            RunTimeError::Thrown { value, line } => {
+                write!(f, "uncaught exception {} at line {}", value, line)
+            }
+            RunTimeError::Return(_) => write!(f, "cannot return from top-level code"),
+            RunTimeError::Break(_) => write!(f, "cannot break outside of a loop"),
+            RunTimeError::Continue(_) => write!(f, "cannot continue outside of a loop"),
         }
     }
 }
 
-impl Error for RunTimeError {}
+impl RunTimeError {
+    //return, break and continue only unwind, so try never intercepts them
+    pub fn is_catchable(&self) -> bool {
+        !matches!(
+            self,
+            RunTimeError::Return(_) | RunTimeError::Break(_) | RunTimeError::Continue(_)
+        )
+    }
+
+    //environment errors don't know where they happened, so the caller attaches the line
+    pub fn environment(line: usize) -> impl Fn(EnvironmentError) -> RunTimeError {
+        move |error| RunTimeError::EnvironmentError { error, line }
+    }
 
-impl From<EnvironmentError> for RunTimeError {
-    fn from(value: EnvironmentError) -> Self {
-        RunTimeError::EnvironmentError(value)
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            RunTimeError::EnvironmentError { line, .. }
+            | RunTimeError::NotCallable { line }
+            | RunTimeError::InvalidArgumentCount { line, .. }
+            | RunTimeError::UndefinedProperty { line, .. }
+            | RunTimeError::InvalidPropertyAccess { line }
+            | RunTimeError::InvalidSuperclass { line }
+            | RunTimeError::IndexOutOfBounds { line, .. }
+            | RunTimeError::InvalidIndex { line }
+            | RunTimeError::NotIndexable { line }
+            | RunTimeError::InvalidKey { line }
+            | RunTimeError::UndefinedKey { line, .. }
+            | RunTimeError::IntegerOverflow { line, .. }
+            | RunTimeError::DivisionByZero { line }
+            | RunTimeError::InvalidShift { line, .. }
+            | RunTimeError::NoMatchingArm { line, .. }
+            | RunTimeError::InvalidPatternClass { line }
+            | RunTimeError::ModuleNotFound { line, .. }
+            | RunTimeError::ImportCycle { line, .. }
+            | RunTimeError::InvalidModule { line, .. }
+            | RunTimeError::Thrown { line, .. } => Some(*line),
+            _ => None,
+        }
     }
 }
+
+impl Error for RunTimeError {}
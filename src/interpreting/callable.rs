@@ -0,0 +1,96 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    environment::environment::Environment,
+    errors::runtime_error::RunTimeError,
+    interpreting::{interpreter::Interpreter, value::Value},
+    parsing::ast::FunctionDecl,
+};
+
+pub(crate) trait Callable {
+    fn arity(&self) -> usize;
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+    ) -> Result<Value, RunTimeError>;
+}
+
+pub struct Function {
+    pub(crate) declaration: Rc<FunctionDecl>,
+    pub(crate) closure: Rc<RefCell<Environment>>,
+    pub(crate) is_initializer: bool,
+}
+
+impl Function {
+    pub(crate) fn new(
+        declaration: Rc<FunctionDecl>,
+        closure: Rc<RefCell<Environment>>,
+        is_initializer: bool,
+    ) -> Self {
+        Function {
+            declaration,
+            closure,
+            is_initializer,
+        }
+    }
+
+    //wraps the closure in a scope where this refers to the given instance
+    pub(crate) fn bind(&self, instance: Value) -> Function {
+        let mut environment = Environment::new_enclosed(Rc::clone(&self.closure));
+        environment
+            .define("this".to_string(), instance)
+            .expect("this is defined into an empty scope");
+        Function::new(
+            Rc::clone(&self.declaration),
+            Rc::new(RefCell::new(environment)),
+            self.is_initializer,
+        )
+    }
+}
+
+impl Callable for Function {
+    fn arity(&self) -> usize {
+        self.declaration.params.len()
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+    ) -> Result<Value, RunTimeError> {
+        let mut environment = Environment::new_enclosed(Rc::clone(&self.closure));
+        for (param, argument) in self.declaration.params.iter().zip(arguments) {
+            environment
+                .define(param.to_string(), argument)
+                .map_err(RunTimeError::environment(self.declaration.line))?;
+        }
+
+        let result =
+            interpreter.execute_block(&self.declaration.body, Rc::new(RefCell::new(environment)));
+
+        //init always hands back the instance, even on an early return
+        if self.is_initializer {
+            return match result {
+                Ok(_) | Err(RunTimeError::Return(_)) => Ok(self
+                    .closure
+                    .borrow_mut()
+                    .get("this".to_string())
+                    .map_err(RunTimeError::environment(self.declaration.line))?),
+                Err(e) => Err(e),
+            };
+        }
+
+        match result {
+            Ok(_) => Ok(Value::Null),
+            Err(RunTimeError::Return(value)) => Ok(value),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl std::fmt::Debug for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<fn {}>", self.declaration.name)
+    }
+}
@@ -0,0 +1,133 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    errors::runtime_error::RunTimeError,
+    interpreting::{
+        callable::{Callable, Function},
+        interpreter::Interpreter,
+        value::Value,
+    },
+};
+
+pub struct Class {
+    pub(crate) name: String,
+    pub(crate) superclass: Option<Rc<Class>>,
+    pub(crate) methods: HashMap<String, Rc<Function>>,
+}
+
+impl Class {
+    pub(crate) fn new(
+        name: String,
+        superclass: Option<Rc<Class>>,
+        methods: HashMap<String, Rc<Function>>,
+    ) -> Self {
+        Class {
+            name,
+            superclass,
+            methods,
+        }
+    }
+
+    //walks up the superclass chain so subclasses inherit anything they don't override
+    pub(crate) fn find_method(&self, name: &str) -> Option<Rc<Function>> {
+        match self.methods.get(name) {
+            Some(method) => Some(Rc::clone(method)),
+            None => match &self.superclass {
+                Some(superclass) => superclass.find_method(name),
+                None => None,
+            },
+        }
+    }
+
+    pub(crate) fn is_subclass_of(&self, other: &Class) -> bool {
+        if std::ptr::eq(self, other) {
+            return true;
+        }
+        match &self.superclass {
+            Some(superclass) => superclass.is_subclass_of(other),
+            None => false,
+        }
+    }
+}
+
+impl Callable for Rc<Class> {
+    fn arity(&self) -> usize {
+        match self.find_method("init") {
+            Some(initializer) => initializer.arity(),
+            None => 0,
+        }
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Value>,
+    ) -> Result<Value, RunTimeError> {
+        let instance = Value::Instance(Rc::new(RefCell::new(Instance::new(Rc::clone(self)))));
+
+        if let Some(initializer) = self.find_method("init") {
+            initializer
+                .bind(instance.clone())
+                .call(interpreter, arguments)?;
+        }
+
+        Ok(instance)
+    }
+}
+
+impl std::fmt::Debug for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<class {}>", self.name)
+    }
+}
+
+pub struct Instance {
+    pub(crate) class: Rc<Class>,
+    fields: HashMap<String, Value>,
+}
+
+impl Instance {
+    pub(crate) fn new(class: Rc<Class>) -> Self {
+        Instance {
+            class,
+            fields: HashMap::new(),
+        }
+    }
+
+    //fields shadow methods, methods come back bound to the instance they were accessed on
+    pub(crate) fn get(
+        instance: &Rc<RefCell<Instance>>,
+        name: &str,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        if let Some(value) = instance.borrow().fields.get(name) {
+            return Ok(value.clone());
+        }
+
+        let method = instance.borrow().class.find_method(name);
+        match method {
+            Some(method) => Ok(Value::Function(Rc::new(
+                method.bind(Value::Instance(Rc::clone(instance))),
+            ))),
+            None => Err(RunTimeError::UndefinedProperty {
+                name: name.to_string(),
+                line,
+            }),
+        }
+    }
+
+    //only looks at fields, unlike get which also finds methods
+    pub(crate) fn field(&self, name: &str) -> Option<Value> {
+        self.fields.get(name).cloned()
+    }
+
+    pub(crate) fn set(&mut self, name: String, value: Value) {
+        self.fields.insert(name, value);
+    }
+}
+
+impl std::fmt::Debug for Instance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{} instance>", self.class.name)
+    }
+}
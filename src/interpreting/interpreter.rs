@@ -1,201 +1,1219 @@
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
 use crate::{
+    checking::checker::check,
     environment::environment::Environment,
-    errors::environment_error::EnvironmentError,
     errors::runtime_error::RunTimeError,
-    interpreting::value::Value,
-    parsing::ast::{BinaryOp, Expr, Literal, Stmt, UnaryOp},
+    interpreting::{
+        callable::{Callable, Function},
+        class::{Class, Instance},
+        module::Module,
+        value::{MapKey, Value},
+    },
+    lexing::lexer::lex_program,
+    parsing::ast::{BinaryOp, Expr, FunctionDecl, Literal, MatchArm, Pattern, Stmt, UnaryOp},
+    parsing::parser::parse_tokens,
+    resolving::resolver::resolve,
 };
 
-struct Interpreter {
-    environment: Environment,
+pub(crate) struct Interpreter {
+    environment: Rc<RefCell<Environment>>,
+    //expression id to scope distance, anything missing is a global
+    locals: HashMap<usize, usize>,
+    //line of the last operator or variable evaluated, given to caught errors that don't carry one
+    line: usize,
+    //class of the objects built-in runtime errors are caught as
+    error_class: Rc<Class>,
+    //keyed by canonical path so every import of a file shares one module
+    modules: HashMap<PathBuf, Rc<Module>>,
+    //files currently executing, innermost last. imports resolve relative to the last one
+    module_stack: Vec<PathBuf>,
 }
 
 impl Interpreter {
-    pub fn new(environment: Environment) -> Self {
+    pub fn new(environment: Environment, locals: HashMap<usize, usize>) -> Self {
+        let mut locals = locals;
+        let error_class = error_class(&mut locals);
+        let mut environment = environment;
+        define_builtins(&mut environment, &error_class);
+
         Interpreter {
-            environment: environment,
+            environment: Rc::new(RefCell::new(environment)),
+            locals,
+            line: 0,
+            error_class,
+            modules: HashMap::new(),
+            module_stack: Vec::new(),
         }
     }
 
+    fn look_up_variable(
+        &mut self,
+        name: String,
+        id: usize,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let value = match self.locals.get(&id) {
+            Some(distance) => self.environment.borrow_mut().get_at(*distance, name),
+            None => self.environment.borrow_mut().get_global(name),
+        };
+        value.map_err(RunTimeError::environment(line))
+    }
+
     fn eval_literal(&mut self, literal: Literal) -> Result<Value, RunTimeError> {
         match literal {
+            Literal::Integer(i) => Ok(Value::Integer(i)),
             Literal::Number(n) => Ok(Value::Number(n)),
             Literal::StringLiteral(s) => Ok(Value::String(s)),
             Literal::True => Ok(Value::Boolean(true)),
             Literal::False => Ok(Value::Boolean(false)),
             Literal::Null => Ok(Value::Null),
-            _ => Err(RunTimeError::CouldNotEval(literal.to_string())), //just in case even though above is exhausting match
-        }
-    }
-
-    fn eval_grouping(&mut self, expr: Expr) -> Result<Value, RunTimeError> {
-        match expr {
-            Expr::Grouping { exp } => self.evaluate(*exp),
-            _ => return Err(RunTimeError::CouldNotEval(expr.to_string())),
         }
     }
 
     fn is_equal(&mut self, v1: Value, v2: Value) -> Result<bool, RunTimeError> {
         match (v1, v2) {
-            (Value::Number(n1), Value::Number(n2)) => Ok(n1 == n2),
+            (
+                v1 @ (Value::Integer(_) | Value::Number(_)),
+                v2 @ (Value::Integer(_) | Value::Number(_)),
+            ) => Ok(v1.numeric_cmp(&v2) == Some(Ordering::Equal)),
             (Value::String(s1), Value::String(s2)) => Ok(s1 == s2),
             (Value::Boolean(b1), Value::Boolean(b2)) => Ok(b1 == b2),
             (_, _) => Err(RunTimeError::CouldNotEval("==".to_string())),
         }
     }
 
+    //integers stay integers and error on overflow, anything mixed with a float becomes a float
+    fn eval_arithmetic(
+        &mut self,
+        op: BinaryOp,
+        left: Value,
+        right: Value,
+        name: &str,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        match (left, right) {
+            (Value::String(s1), Value::String(s2)) if matches!(op, BinaryOp::Plus) => {
+                Ok(Value::String(s1 + &s2))
+            }
+            (Value::Integer(i1), Value::Integer(i2)) => {
+                let result = match op {
+                    BinaryOp::Plus => i1.checked_add(i2),
+                    BinaryOp::Minus => i1.checked_sub(i2),
+                    BinaryOp::Star => i1.checked_mul(i2),
+                    //truncates toward zero, and % keeps the sign of the left side to match
+                    BinaryOp::Slash | BinaryOp::Percent if i2 == 0 => {
+                        return Err(RunTimeError::DivisionByZero { line });
+                    }
+                    BinaryOp::Slash => i1.checked_div(i2),
+                    BinaryOp::Percent => i1.checked_rem(i2),
+                    //a negative exponent can't stay an integer
+                    BinaryOp::StarStar if i2 < 0 => {
+                        return Ok(Value::Number((i1 as f64).powf(i2 as f64)));
+                    }
+                    BinaryOp::StarStar => match u32::try_from(i2) {
+                        Ok(exponent) => i1.checked_pow(exponent),
+                        Err(_) => None,
+                    },
+                    _ => return Err(RunTimeError::CouldNotEval(name.to_string())),
+                };
+                match result {
+                    Some(i) => Ok(Value::Integer(i)),
+                    None => Err(RunTimeError::IntegerOverflow {
+                        op: op.to_string(),
+                        line,
+                    }),
+                }
+            }
+            (left, right) => match (left.as_f64(), right.as_f64()) {
+                (Some(n1), Some(n2)) => match op {
+                    BinaryOp::Plus => Ok(Value::Number(n1 + n2)),
+                    BinaryOp::Minus => Ok(Value::Number(n1 - n2)),
+                    BinaryOp::Star => Ok(Value::Number(n1 * n2)),
+                    BinaryOp::Slash => Ok(Value::Number(n1 / n2)),
+                    BinaryOp::Percent => Ok(Value::Number(n1 % n2)),
+                    BinaryOp::StarStar => Ok(Value::Number(n1.powf(n2))),
+                    _ => Err(RunTimeError::CouldNotEval(name.to_string())),
+                },
+                _ => Err(RunTimeError::CouldNotEval(name.to_string())),
+            },
+        }
+    }
+
+    fn eval_bitwise(
+        &mut self,
+        op: BinaryOp,
+        left: Value,
+        right: Value,
+        name: &str,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let (i1, i2) = match (left, right) {
+            (Value::Integer(i1), Value::Integer(i2)) => (i1, i2),
+            (_, _) => return Err(RunTimeError::CouldNotEval(name.to_string())),
+        };
+
+        match op {
+            BinaryOp::Ampersand => Ok(Value::Integer(i1 & i2)),
+            BinaryOp::Pipe => Ok(Value::Integer(i1 | i2)),
+            BinaryOp::Caret => Ok(Value::Integer(i1 ^ i2)),
+            BinaryOp::LessLess | BinaryOp::GreaterGreater => {
+                let amount = match u32::try_from(i2) {
+                    Ok(amount) if amount < i64::BITS => amount,
+                    _ => return Err(RunTimeError::InvalidShift { amount: i2, line }),
+                };
+                match op {
+                    BinaryOp::LessLess => Ok(Value::Integer(i1 << amount)),
+                    //arithmetic shift, the sign bit is preserved
+                    _ => Ok(Value::Integer(i1 >> amount)),
+                }
+            }
+            _ => Err(RunTimeError::CouldNotEval(name.to_string())),
+        }
+    }
+
+    fn eval_comparison(
+        &mut self,
+        op: BinaryOp,
+        left: Value,
+        right: Value,
+        name: &str,
+    ) -> Result<Value, RunTimeError> {
+        let ordering = match (&left, &right) {
+            (Value::Integer(_) | Value::Number(_), Value::Integer(_) | Value::Number(_)) => {
+                left.numeric_cmp(&right)
+            }
+            _ => return Err(RunTimeError::CouldNotEval(name.to_string())),
+        };
+
+        //NaN compares false against everything
+        let result = match ordering {
+            Some(ordering) => match op {
+                BinaryOp::GreaterThan => ordering.is_gt(),
+                BinaryOp::GreaterEqual => ordering.is_ge(),
+                BinaryOp::LessThan => ordering.is_lt(),
+                BinaryOp::LessEqual => ordering.is_le(),
+                _ => return Err(RunTimeError::CouldNotEval(name.to_string())),
+            },
+            None => false,
+        };
+        Ok(Value::Boolean(result))
+    }
+
     fn eval_binary(
         &mut self,
         left: Expr,
         op: BinaryOp,
         right: Expr,
+        line: usize,
     ) -> Result<Value, RunTimeError> {
-        let left = match self.evaluate(left) {
-            Ok(left) => left,
-            Err(e) => return Err(e),
-        };
-        let right = match self.evaluate(right) {
-            Ok(right) => right,
-            Err(e) => return Err(e),
-        };
+        let left = self.evaluate(left)?;
+        let right = self.evaluate(right)?;
+        self.line = line;
         match op {
-            BinaryOp::Minus => match (left, right) {
-                (Value::Number(n1), Value::Number(n2)) => Ok(Value::Number(n1 - n2)),
-                (_, _) => Err(RunTimeError::CouldNotEval("minus".to_string())),
-            },
-            BinaryOp::Slash => match (left, right) {
-                (Value::Number(n1), Value::Number(n2)) => Ok(Value::Number(n1 / n2)),
-                (_, _) => Err(RunTimeError::CouldNotEval("slash".to_string())),
-            },
-            BinaryOp::Star => match (left, right) {
-                (Value::Number(n1), Value::Number(n2)) => Ok(Value::Number(n1 * n2)),
-                (_, _) => Err(RunTimeError::CouldNotEval("star".to_string())),
-            },
-            BinaryOp::Plus => match (left, right) {
-                //can add additional conversions and abilities in this later
-                (Value::Number(n1), Value::Number(n2)) => Ok(Value::Number(n1 + n2)),
-                (Value::String(s1), Value::String(s2)) => Ok(Value::String(s1 + &s2)),
-                (_, _) => Err(RunTimeError::CouldNotEval("plus".to_string())),
-            },
-            BinaryOp::GreaterThan => match (left, right) {
-                (Value::Number(n1), Value::Number(n2)) => Ok(Value::Boolean(n1 > n2)),
-                (_, _) => Err(RunTimeError::CouldNotEval(">".to_string())),
-            },
-            BinaryOp::GreaterEqual => match (left, right) {
-                (Value::Number(n1), Value::Number(n2)) => Ok(Value::Boolean(n1 >= n2)),
-                (_, _) => Err(RunTimeError::CouldNotEval(">=".to_string())),
-            },
-            BinaryOp::LessThan => match (left, right) {
-                (Value::Number(n1), Value::Number(n2)) => Ok(Value::Boolean(n1 < n2)),
-                (_, _) => Err(RunTimeError::CouldNotEval("<".to_string())),
-            },
-            BinaryOp::LessEqual => match (left, right) {
-                (Value::Number(n1), Value::Number(n2)) => Ok(Value::Boolean(n1 <= n2)),
-                (_, _) => Err(RunTimeError::CouldNotEval(">".to_string())),
-            },
-            BinaryOp::EqualEqual => match self.is_equal(left, right) {
-                Ok(b) => Ok(Value::Boolean(b)),
-                Err(e) => Err(e),
-            },
-            BinaryOp::BangEqual => match self.is_equal(left, right) {
-                Ok(b) => Ok(Value::Boolean(!b)),
-                Err(e) => Err(e),
-            },
+            BinaryOp::Minus => self.eval_arithmetic(op, left, right, "minus", line),
+            BinaryOp::Slash => self.eval_arithmetic(op, left, right, "slash", line),
+            BinaryOp::Star => self.eval_arithmetic(op, left, right, "star", line),
+            //can add additional conversions and abilities in this later
+            BinaryOp::Plus => self.eval_arithmetic(op, left, right, "plus", line),
+            BinaryOp::Percent => self.eval_arithmetic(op, left, right, "percent", line),
+            BinaryOp::StarStar => self.eval_arithmetic(op, left, right, "power", line),
+            BinaryOp::Ampersand => self.eval_bitwise(op, left, right, "&", line),
+            BinaryOp::Pipe => self.eval_bitwise(op, left, right, "|", line),
+            BinaryOp::Caret => self.eval_bitwise(op, left, right, "^", line),
+            BinaryOp::LessLess => self.eval_bitwise(op, left, right, "<<", line),
+            BinaryOp::GreaterGreater => self.eval_bitwise(op, left, right, ">>", line),
+            BinaryOp::GreaterThan => self.eval_comparison(op, left, right, ">"),
+            BinaryOp::GreaterEqual => self.eval_comparison(op, left, right, ">="),
+            BinaryOp::LessThan => self.eval_comparison(op, left, right, "<"),
+            BinaryOp::LessEqual => self.eval_comparison(op, left, right, "<="),
+            BinaryOp::EqualEqual => Ok(Value::Boolean(self.is_equal(left, right)?)),
+            BinaryOp::BangEqual => Ok(Value::Boolean(!self.is_equal(left, right)?)),
             _ => Err(RunTimeError::CouldNotEval("Operator not found".to_string())),
         }
     }
 
-    fn eval_unary(&mut self, op: UnaryOp, right: Expr) -> Result<Value, RunTimeError> {
-        let right = match self.evaluate(right) {
-            Ok(right) => right,
-            Err(e) => return Err(e),
-        };
+    //returns whichever operand decided the result, the right side is only evaluated if needed
+    fn eval_logical(
+        &mut self,
+        left: Expr,
+        op: BinaryOp,
+        right: Expr,
+    ) -> Result<Value, RunTimeError> {
+        let left = self.evaluate(left)?;
+        let left_truthy = self.is_truthy(left.clone());
+
+        match op {
+            BinaryOp::Or if left_truthy => Ok(left),
+            BinaryOp::And if !left_truthy => Ok(left),
+            BinaryOp::Or | BinaryOp::And => self.evaluate(right),
+            _ => Err(RunTimeError::CouldNotEval(op.to_string())),
+        }
+    }
+
+    //tests the value against the pattern, pushing any bindings. a value of the wrong shape simply
+    //fails to match rather than erroring
+    fn match_pattern(
+        &mut self,
+        pattern: &Pattern,
+        value: &Value,
+        bindings: &mut Vec<(String, Value)>,
+    ) -> Result<bool, RunTimeError> {
+        match pattern {
+            Pattern::Wildcard => Ok(true),
+            Pattern::Binding(name) => {
+                bindings.push((name.to_string(), value.clone()));
+                Ok(true)
+            }
+            Pattern::Literal(Literal::Null) => Ok(matches!(value, Value::Null)),
+            Pattern::Literal(literal) => {
+                let expected = self.eval_literal(literal.clone())?;
+                Ok(self.is_equal(expected, value.clone()).unwrap_or(false))
+            }
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let start = self.eval_literal(start.clone())?;
+                let end = self.eval_literal(end.clone())?;
+                let after_start: bool = value.numeric_cmp(&start).is_some_and(|o| o.is_ge());
+                let before_end: bool = value
+                    .numeric_cmp(&end)
+                    .is_some_and(|o| if *inclusive { o.is_le() } else { o.is_lt() });
+                Ok(after_start && before_end)
+            }
+            Pattern::List { elements, rest } => {
+                //copied out so nested patterns never run while the list is borrowed
+                let list: Vec<Value> = match value {
+                    Value::List(list) => list.borrow().clone(),
+                    _ => return Ok(false),
+                };
+                if list.len() < elements.len() || (rest.is_none() && list.len() != elements.len()) {
+                    return Ok(false);
+                }
+                for (element, value) in elements.iter().zip(list.iter()) {
+                    if !self.match_pattern(element, value, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                match rest {
+                    Some(rest) => {
+                        let tail =
+                            Value::List(Rc::new(RefCell::new(list[elements.len()..].to_vec())));
+                        self.match_pattern(rest, &tail, bindings)
+                    }
+                    None => Ok(true),
+                }
+            }
+            Pattern::Map(entries) => {
+                let map = match value {
+                    Value::Map(map) => Rc::clone(map),
+                    _ => return Ok(false),
+                };
+                for (key, pattern) in entries {
+                    let key = match self.eval_literal(key.clone())?.to_map_key() {
+                        Some(key) => key,
+                        None => return Ok(false),
+                    };
+                    let entry = map.borrow().get(&key).cloned();
+                    match entry {
+                        Some(entry) if self.match_pattern(pattern, &entry, bindings)? => (),
+                        _ => return Ok(false),
+                    }
+                }
+                Ok(true)
+            }
+            Pattern::Instance {
+                class,
+                fields,
+                line,
+            } => {
+                let class = match self.evaluate(class.clone())? {
+                    Value::Class(class) => class,
+                    _ => return Err(RunTimeError::InvalidPatternClass { line: *line }),
+                };
+                let instance = match value {
+                    Value::Instance(instance) => Rc::clone(instance),
+                    _ => return Ok(false),
+                };
+                if !instance.borrow().class.is_subclass_of(&class) {
+                    return Ok(false);
+                }
+                for (name, pattern) in fields {
+                    let field = instance.borrow().field(name);
+                    match field {
+                        Some(field) if self.match_pattern(pattern, &field, bindings)? => (),
+                        _ => return Ok(false),
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    //None when the guard rejects the arm
+    fn eval_match_arm(
+        &mut self,
+        guard: Option<Expr>,
+        body: Expr,
+    ) -> Result<Option<Value>, RunTimeError> {
+        if let Some(guard) = guard {
+            let guard = self.evaluate(guard)?;
+            if !self.is_truthy(guard) {
+                return Ok(None);
+            }
+        }
+        self.evaluate(body).map(Some)
+    }
+
+    fn eval_match(
+        &mut self,
+        subject: Expr,
+        arms: Vec<MatchArm>,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let subject = self.evaluate(subject)?;
+
+        for arm in arms {
+            let mut bindings: Vec<(String, Value)> = Vec::new();
+            if !self.match_pattern(&arm.pattern, &subject, &mut bindings)? {
+                continue;
+            }
+
+            let mut environment = Environment::new_enclosed(Rc::clone(&self.environment));
+            for (name, value) in bindings {
+                environment
+                    .define(name, value)
+                    .map_err(RunTimeError::environment(line))?;
+            }
+            let previous =
+                std::mem::replace(&mut self.environment, Rc::new(RefCell::new(environment)));
+            let result = self.eval_match_arm(arm.guard, arm.body);
+            self.environment = previous;
+
+            if let Some(value) = result? {
+                return Ok(value);
+            }
+        }
+
+        Err(RunTimeError::NoMatchingArm {
+            value: subject.to_string(),
+            line,
+        })
+    }
+
+    fn eval_conditional(
+        &mut self,
+        condition: Expr,
+        then_branch: Expr,
+        else_branch: Expr,
+    ) -> Result<Value, RunTimeError> {
+        let condition = self.evaluate(condition)?;
+        if self.is_truthy(condition) {
+            self.evaluate(then_branch)
+        } else {
+            self.evaluate(else_branch)
+        }
+    }
+
+    fn eval_unary(&mut self, op: UnaryOp, right: Expr, line: usize) -> Result<Value, RunTimeError> {
+        let right = self.evaluate(right)?;
+        self.line = line;
 
         match op {
             UnaryOp::Minus => match right {
+                Value::Integer(i) => match i.checked_neg() {
+                    Some(i) => Ok(Value::Integer(i)),
+                    None => Err(RunTimeError::IntegerOverflow {
+                        op: op.to_string(),
+                        line,
+                    }),
+                },
                 Value::Number(n) => Ok(Value::Number(-n)),
                 _ => Err(RunTimeError::CouldNotEval("- unary".to_string())),
             },
             UnaryOp::Bang => Ok(Value::Boolean(!self.is_truthy(right))),
+            UnaryOp::Tilde => match right {
+                Value::Integer(i) => Ok(Value::Integer(!i)),
+                _ => Err(RunTimeError::CouldNotEval("~ unary".to_string())),
+            },
+        }
+    }
+
+    fn assign_variable(
+        &mut self,
+        name: String,
+        id: usize,
+        value: Value,
+        line: usize,
+    ) -> Result<(), RunTimeError> {
+        let result = match self.locals.get(&id) {
+            Some(distance) => self
+                .environment
+                .borrow_mut()
+                .assign_at(*distance, name, value),
+            None => self.environment.borrow_mut().assign_global(name, value),
+        };
+        result.map_err(RunTimeError::environment(line))
+    }
+
+    fn eval_assign(
+        &mut self,
+        name: String,
+        value: Expr,
+        id: usize,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let value = self.evaluate(value)?;
+        self.line = line;
+        self.assign_variable(name, id, value.clone(), line)?;
+        Ok(value)
+    }
+
+    //reads the target, computes the new value and writes it back, evaluating the object and index
+    //of the target only once. returns the old and new values
+    fn update_target<F>(
+        &mut self,
+        target: Expr,
+        line: usize,
+        compute: F,
+    ) -> Result<(Value, Value), RunTimeError>
+    where
+        F: FnOnce(&mut Self, Value) -> Result<Value, RunTimeError>,
+    {
+        match target {
+            Expr::Variable { name, id, .. } => {
+                let old = self.look_up_variable(name.clone(), id, line)?;
+                let new = compute(self, old.clone())?;
+                self.assign_variable(name, id, new.clone(), line)?;
+                Ok((old, new))
+            }
+            Expr::Get { object, name, line } => {
+                let instance = match self.evaluate(*object)? {
+                    Value::Instance(instance) => instance,
+                    _ => return Err(RunTimeError::InvalidPropertyAccess { line }),
+                };
+                let old = Instance::get(&instance, &name, line)?;
+                let new = compute(self, old.clone())?;
+                instance.borrow_mut().set(name, new.clone());
+                Ok((old, new))
+            }
+            Expr::Index {
+                object,
+                index,
+                line,
+            } => {
+                let object = self.evaluate(*object)?;
+                let index = self.evaluate(*index)?;
+                let old = self.read_index(&object, index.clone(), line)?;
+                let new = compute(self, old.clone())?;
+                self.write_index(&object, index, new.clone(), line)?;
+                Ok((old, new))
+            }
+            _ => Err(RunTimeError::CouldNotEval(format!(
+                "assignment target at line {}",
+                line
+            ))),
+        }
+    }
+
+    fn eval_compound_assign(
+        &mut self,
+        target: Expr,
+        op: BinaryOp,
+        value: Expr,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let (_, new) = self.update_target(target, line, |interpreter, old| {
+            let value = interpreter.evaluate(value)?;
+            interpreter.line = line;
+            let name = op.to_string();
+            interpreter.eval_arithmetic(op, old, value, &name, line)
+        })?;
+        Ok(new)
+    }
+
+    fn eval_increment(
+        &mut self,
+        target: Expr,
+        op: BinaryOp,
+        prefix: bool,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let (old, new) = self.update_target(target, line, |interpreter, old| {
+            interpreter.line = line;
+            let name = format!("{}{}", op, op);
+            interpreter.eval_arithmetic(op, old, Value::Integer(1), &name, line)
+        })?;
+        if prefix { Ok(new) } else { Ok(old) }
+    }
+
+    fn eval_call(
+        &mut self,
+        callee: Expr,
+        arguments: Vec<Expr>,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let callee = self.evaluate(callee)?;
+
+        let mut values: Vec<Value> = Vec::new();
+        for argument in arguments {
+            values.push(self.evaluate(argument)?);
+        }
+
+        let callable: &dyn Callable = match &callee {
+            Value::Function(function) => function.as_ref(),
+            Value::Class(class) => class,
+            _ => return Err(RunTimeError::NotCallable { line }),
+        };
+
+        if values.len() != callable.arity() {
+            return Err(RunTimeError::InvalidArgumentCount {
+                expected: callable.arity(),
+                found: values.len(),
+                line,
+            });
+        }
+
+        callable.call(self, values)
+    }
+
+    fn eval_get(&mut self, object: Expr, name: String, line: usize) -> Result<Value, RunTimeError> {
+        match self.evaluate(object)? {
+            Value::Instance(instance) => Instance::get(&instance, &name, line),
+            Value::Module(module) => module.get(&name, line),
+            _ => Err(RunTimeError::InvalidPropertyAccess { line }),
+        }
+    }
+
+    //this always lives in the scope directly inside the one holding super
+    fn eval_super(
+        &mut self,
+        method: String,
+        id: usize,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let distance: usize = match self.locals.get(&id) {
+            Some(distance) => *distance,
+            None => return Err(RunTimeError::InvalidSuperclass { line }),
+        };
+        let superclass = match self
+            .environment
+            .borrow_mut()
+            .get_at(distance, "super".to_string())
+            .map_err(RunTimeError::environment(line))?
+        {
+            Value::Class(class) => class,
+            _ => return Err(RunTimeError::InvalidSuperclass { line }),
+        };
+        let instance = self
+            .environment
+            .borrow_mut()
+            .get_at(distance - 1, "this".to_string())
+            .map_err(RunTimeError::environment(line))?;
+
+        match superclass.find_method(&method) {
+            Some(method) => Ok(Value::Function(Rc::new(method.bind(instance)))),
+            None => Err(RunTimeError::UndefinedProperty { name: method, line }),
+        }
+    }
+
+    fn eval_set(
+        &mut self,
+        object: Expr,
+        name: String,
+        value: Expr,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let instance = match self.evaluate(object)? {
+            Value::Instance(instance) => instance,
+            _ => return Err(RunTimeError::InvalidPropertyAccess { line }),
+        };
+
+        let value = self.evaluate(value)?;
+        instance.borrow_mut().set(name, value.clone());
+        Ok(value)
+    }
+
+    //negative indexes count back from the end of the list
+    fn list_index(
+        &mut self,
+        index: Value,
+        length: usize,
+        line: usize,
+    ) -> Result<usize, RunTimeError> {
+        let index: i64 = match index {
+            Value::Integer(i) => i,
+            Value::Number(n) if n.fract() == 0.0 => n as i64,
+            _ => return Err(RunTimeError::InvalidIndex { line }),
+        };
+
+        let position: i64 = if index < 0 {
+            index + length as i64
+        } else {
+            index
+        };
+        if position < 0 || position >= length as i64 {
+            return Err(RunTimeError::IndexOutOfBounds {
+                index,
+                length,
+                line,
+            });
+        }
+        Ok(position as usize)
+    }
+
+    fn eval_interpolation(&mut self, parts: Vec<Expr>) -> Result<Value, RunTimeError> {
+        let mut result = String::new();
+        for part in parts {
+            result.push_str(&self.evaluate(part)?.to_string());
+        }
+        Ok(Value::String(result))
+    }
+
+    fn eval_list(&mut self, elements: Vec<Expr>) -> Result<Value, RunTimeError> {
+        let mut values: Vec<Value> = Vec::new();
+        for element in elements {
+            values.push(self.evaluate(element)?);
+        }
+        Ok(Value::List(Rc::new(RefCell::new(values))))
+    }
+
+    fn eval_map(&mut self, entries: Vec<(Expr, Expr)>, line: usize) -> Result<Value, RunTimeError> {
+        let mut values: HashMap<MapKey, Value> = HashMap::new();
+        for (key, value) in entries {
+            let key = self
+                .evaluate(key)?
+                .to_map_key()
+                .ok_or(RunTimeError::InvalidKey { line })?;
+            let value = self.evaluate(value)?;
+            values.insert(key, value);
+        }
+        Ok(Value::Map(Rc::new(RefCell::new(values))))
+    }
+
+    fn eval_index(
+        &mut self,
+        object: Expr,
+        index: Expr,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        self.read_index(&object, index, line)
+    }
+
+    fn read_index(
+        &mut self,
+        object: &Value,
+        index: Value,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        match object {
+            Value::List(elements) => {
+                let length = elements.borrow().len();
+                let position = self.list_index(index, length, line)?;
+                Ok(elements.borrow()[position].clone())
+            }
+            Value::Map(entries) => {
+                let key = index
+                    .to_map_key()
+                    .ok_or(RunTimeError::InvalidKey { line })?;
+                match entries.borrow().get(&key) {
+                    Some(value) => Ok(value.clone()),
+                    None => Err(RunTimeError::UndefinedKey {
+                        key: key.to_string(),
+                        line,
+                    }),
+                }
+            }
+            _ => Err(RunTimeError::NotIndexable { line }),
+        }
+    }
+
+    fn eval_index_set(
+        &mut self,
+        object: Expr,
+        index: Expr,
+        value: Expr,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        let value = self.evaluate(value)?;
+        self.write_index(&object, index, value, line)
+    }
+
+    fn write_index(
+        &mut self,
+        object: &Value,
+        index: Value,
+        value: Value,
+        line: usize,
+    ) -> Result<Value, RunTimeError> {
+        match object {
+            Value::List(elements) => {
+                let length = elements.borrow().len();
+                let position = self.list_index(index, length, line)?;
+                elements.borrow_mut()[position] = value.clone();
+                Ok(value)
+            }
+            Value::Map(entries) => {
+                let key = index
+                    .to_map_key()
+                    .ok_or(RunTimeError::InvalidKey { line })?;
+                entries.borrow_mut().insert(key, value.clone());
+                Ok(value)
+            }
+            _ => Err(RunTimeError::NotIndexable { line }),
         }
     }
 
     pub fn evaluate(&mut self, exp: Expr) -> Result<Value, RunTimeError> {
         match exp {
-            Expr::Binary { left, op, right } => match self.eval_binary(*left, op, *right) {
-                Ok(val) => Ok(val),
-                Err(e) => return Err(e),
-            },
-            Expr::Unary { op, right } => match self.eval_unary(op, *right) {
-                Ok(val) => Ok(val),
-                Err(e) => return Err(e),
-            },
-            Expr::Grouping { exp } => match self.eval_grouping(*exp) {
-                Ok(val) => Ok(val),
-                Err(e) => return Err(e),
-            },
-            Expr::Literal(literal) => match self.eval_literal(literal) {
-                Ok(val) => Ok(val),
-                Err(e) => return Err(e),
-            },
-            _ => Err(RunTimeError::CouldNotEval(exp.to_string())),
+            Expr::Binary {
+                left,
+                op,
+                right,
+                line,
+            } => self.eval_binary(*left, op, *right, line),
+            Expr::Logical { left, op, right } => self.eval_logical(*left, op, *right),
+            Expr::Match {
+                subject,
+                arms,
+                line,
+            } => self.eval_match(*subject, arms, line),
+            Expr::Lambda(declaration) => Ok(Value::Function(Rc::new(Function::new(
+                declaration,
+                Rc::clone(&self.environment),
+                false,
+            )))),
+            Expr::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => self.eval_conditional(*condition, *then_branch, *else_branch),
+            Expr::Unary { op, right, line } => self.eval_unary(op, *right, line),
+            Expr::Grouping { exp } => self.evaluate(*exp),
+            Expr::Literal(literal) => self.eval_literal(literal),
+            Expr::Variable { name, id, line } => {
+                self.line = line;
+                self.look_up_variable(name, id, line)
+            }
+            Expr::Assign {
+                name,
+                value,
+                id,
+                line,
+            } => self.eval_assign(name, *value, id, line),
+            Expr::Call {
+                callee,
+                arguments,
+                line,
+            } => self.eval_call(*callee, arguments, line),
+            Expr::Get { object, name, line } => self.eval_get(*object, name, line),
+            Expr::Set {
+                object,
+                name,
+                value,
+                line,
+            } => self.eval_set(*object, name, *value, line),
+            Expr::Interpolation(parts) => self.eval_interpolation(parts),
+            Expr::List(elements) => self.eval_list(elements),
+            Expr::Map { entries, line } => self.eval_map(entries, line),
+            Expr::Index {
+                object,
+                index,
+                line,
+            } => self.eval_index(*object, *index, line),
+            Expr::IndexSet {
+                object,
+                index,
+                value,
+                line,
+            } => self.eval_index_set(*object, *index, *value, line),
+            Expr::CompoundAssign {
+                target,
+                op,
+                value,
+                line,
+            } => self.eval_compound_assign(*target, op, *value, line),
+            Expr::Increment {
+                target,
+                op,
+                prefix,
+                line,
+            } => self.eval_increment(*target, op, prefix, line),
+            Expr::This { id, line } => self.look_up_variable("this".to_string(), id, line),
+            Expr::Super { method, id, line } => self.eval_super(method, id, line),
         }
     }
 
     pub fn execute(&mut self, stmt: &Stmt) -> Result<(), RunTimeError> {
         match stmt {
-            Stmt::Expression(e) => match self.evaluate(e.clone()) {
-                Ok(e) => println!("{}", e), //for testing don't acutally print though in practice
-                Err(err) => return Err(err),
-            },
-            Stmt::Print(e) => match self.evaluate(e.clone()) {
-                Ok(e) => println!("{}", e),
-                Err(err) => return Err(err),
-            },
-            Stmt::Var { name, initializer } => {
-                let val;
-                match initializer {
-                    Some(initializer) => {
-                        val = match self.evaluate(initializer.clone()) {
-                            Ok(v) => Some(v),
-                            Err(err) => return Err(err),
-                        };
-                    }
-                    None => val = Some(Value::Null),
+            Stmt::Expression(e) => {
+                self.evaluate(e.clone())?;
+            }
+            Stmt::Print(e) => {
+                let val = self.evaluate(e.clone())?;
+                println!("{}", val)
+            }
+            Stmt::Var {
+                name,
+                initializer,
+                mutable,
+                line,
+                ..
+            } => {
+                let val = match initializer {
+                    Some(initializer) => self.evaluate(initializer.clone())?,
+                    None => Value::Null,
+                };
+
+                let result = if *mutable {
+                    self.environment.borrow_mut().define(name.to_string(), val)
+                } else {
+                    self.environment
+                        .borrow_mut()
+                        .define_constant(name.to_string(), val)
+                };
+                result.map_err(RunTimeError::environment(*line))?
+            }
+            Stmt::Block(statements) => {
+                let block_env = Environment::new_enclosed(Rc::clone(&self.environment));
+                self.execute_block(statements, Rc::new(RefCell::new(block_env)))?
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let condition = self.evaluate(condition.clone())?;
+                if self.is_truthy(condition) {
+                    self.execute(then_branch)?
+                } else if let Some(else_branch) = else_branch {
+                    self.execute(else_branch)?
+                }
+            }
+            Stmt::While {
+                condition,
+                body,
+                increment,
+                label,
+            } => loop {
+                let condition = self.evaluate(condition.clone())?;
+                if !self.is_truthy(condition) {
+                    break;
                 }
 
-                match val {
-                    Some(v) => self.environment.define(name.to_string(), v)?,
-                    None => {
-                        return Err(RunTimeError::EnvironmentError(
-                            EnvironmentError::UndefinedVariable(name.to_string()),
-                        ));
+                //an unlabeled break/continue targets the innermost loop, a labeled one keeps
+                //unwinding until it reaches the loop with that label
+                match self.execute(body) {
+                    Ok(_) => (),
+                    Err(RunTimeError::Break(target)) if target.is_none() || target == *label => {
+                        break;
+                    }
+                    Err(RunTimeError::Continue(target)) if target.is_none() || target == *label => {
                     }
+                    Err(e) => return Err(e),
                 }
+
+                if let Some(increment) = increment {
+                    self.evaluate(increment.clone())?;
+                }
+            },
+            Stmt::Break { label } => return Err(RunTimeError::Break(label.clone())),
+            Stmt::Continue { label } => return Err(RunTimeError::Continue(label.clone())),
+            Stmt::Function(declaration) => {
+                //the closure captures the scope the function was declared in
+                let function =
+                    Function::new(Rc::clone(declaration), Rc::clone(&self.environment), false);
+                self.environment
+                    .borrow_mut()
+                    .define(
+                        declaration.name.to_string(),
+                        Value::Function(Rc::new(function)),
+                    )
+                    .map_err(RunTimeError::environment(declaration.line))?
+            }
+            Stmt::Class {
+                name,
+                superclass,
+                methods,
+                line,
+            } => {
+                let superclass: Option<Rc<Class>> = match superclass {
+                    Some(superclass) => match self.evaluate(superclass.clone())? {
+                        Value::Class(class) => Some(class),
+                        _ => return Err(RunTimeError::InvalidSuperclass { line: *line }),
+                    },
+                    None => None,
+                };
+
+                //methods of a subclass close over an extra scope that holds super
+                let mut method_env = Rc::clone(&self.environment);
+                if let Some(superclass) = &superclass {
+                    let mut super_env = Environment::new_enclosed(Rc::clone(&self.environment));
+                    super_env
+                        .define("super".to_string(), Value::Class(Rc::clone(superclass)))
+                        .expect("super is defined into an empty scope");
+                    method_env = Rc::new(RefCell::new(super_env));
+                }
+
+                let mut class_methods: HashMap<String, Rc<Function>> = HashMap::new();
+                for method in methods {
+                    let function = Function::new(
+                        Rc::clone(method),
+                        Rc::clone(&method_env),
+                        method.name == "init",
+                    );
+                    class_methods.insert(method.name.to_string(), Rc::new(function));
+                }
+
+                let class = Class::new(name.to_string(), superclass, class_methods);
+                self.environment
+                    .borrow_mut()
+                    .define(name.to_string(), Value::Class(Rc::new(class)))
+                    .map_err(RunTimeError::environment(*line))?
+            }
+            Stmt::Throw { value, line } => {
+                let value = self.evaluate(value.clone())?;
+                return Err(RunTimeError::Thrown { value, line: *line });
+            }
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+            } => self.execute_try(body, catch, finally)?,
+            Stmt::Import {
+                path,
+                alias,
+                names,
+                line,
+            } => self.execute_import(path, alias, names, *line)?,
+            Stmt::Return { value, .. } => {
+                let value = match value {
+                    Some(value) => self.evaluate(value.clone())?,
+                    None => Value::Null,
+                };
+                return Err(RunTimeError::Return(value));
             }
         }
         Ok(())
     }
 
+    //runs the file in its own global scope the first time it is imported and caches the result
+    fn load_module(&mut self, path: &str, line: usize) -> Result<Rc<Module>, RunTimeError> {
+        let base: PathBuf = match self
+            .module_stack
+            .last()
+            .and_then(|current| current.parent())
+        {
+            Some(directory) => directory.to_path_buf(),
+            None => PathBuf::new(),
+        };
+        let not_found = || RunTimeError::ModuleNotFound {
+            path: path.to_string(),
+            line,
+        };
+        let module_path: PathBuf = fs::canonicalize(base.join(path)).map_err(|_| not_found())?;
+
+        if self.module_stack.contains(&module_path) {
+            return Err(RunTimeError::ImportCycle {
+                path: path.to_string(),
+                line,
+            });
+        }
+        if let Some(module) = self.modules.get(&module_path) {
+            return Ok(Rc::clone(module));
+        }
+
+        let source: String = fs::read_to_string(&module_path).map_err(|_| not_found())?;
+        let invalid = |error: String| RunTimeError::InvalidModule {
+            path: path.to_string(),
+            error,
+            line,
+        };
+        let tokens = lex_program(&source).map_err(|e| invalid(e.to_string()))?;
+        let statements = parse_tokens(&tokens).map_err(|e| invalid(e.to_string()))?;
+        let locals =
+            resolve(&statements, Some(&module_path)).map_err(|e| invalid(e.to_string()))?;
+        check(&statements).map_err(|e| invalid(e.to_string()))?;
+        //expression ids are unique across files so the module's entries never collide
+        self.locals.extend(locals);
+
+        let mut environment = Environment::new();
+        define_builtins(&mut environment, &self.error_class);
+        let environment = Rc::new(RefCell::new(environment));
+
+        self.module_stack.push(module_path.clone());
+        let result = self.execute_block(&statements, Rc::clone(&environment));
+        self.module_stack.pop();
+        result?;
+
+        let module = Rc::new(Module::new(path.to_string(), environment));
+        self.modules.insert(module_path, Rc::clone(&module));
+        Ok(module)
+    }
+
+    fn execute_import(
+        &mut self,
+        path: &str,
+        alias: &Option<String>,
+        names: &Vec<String>,
+        line: usize,
+    ) -> Result<(), RunTimeError> {
+        let module = self.load_module(path, line)?;
+
+        if let Some(alias) = alias {
+            self.environment
+                .borrow_mut()
+                .define(alias.to_string(), Value::Module(module))
+                .map_err(RunTimeError::environment(line))?;
+            return Ok(());
+        }
+        for name in names {
+            let value = module.get(name, line)?;
+            let mut environment = self.environment.borrow_mut();
+            let result = match module.is_constant(name) {
+                true => environment.define_constant(name.to_string(), value),
+                false => environment.define(name.to_string(), value),
+            };
+            result.map_err(RunTimeError::environment(line))?;
+        }
+        Ok(())
+    }
+
+    //thrown values are caught as themselves, built-in errors become Error instances
+    fn error_value(&mut self, error: RunTimeError) -> Value {
+        if let RunTimeError::Thrown { value, .. } = error {
+            return value;
+        }
+
+        let line = error.line().unwrap_or(self.line);
+        let mut instance = Instance::new(Rc::clone(&self.error_class));
+        instance.set("message".to_string(), Value::String(error.to_string()));
+        instance.set("line".to_string(), Value::Integer(line as i64));
+        Value::Instance(Rc::new(RefCell::new(instance)))
+    }
+
+    fn execute_try(
+        &mut self,
+        body: &Vec<Stmt>,
+        catch: &Option<(String, Vec<Stmt>)>,
+        finally: &Option<Vec<Stmt>>,
+    ) -> Result<(), RunTimeError> {
+        let environment = Environment::new_enclosed(Rc::clone(&self.environment));
+        let mut result = self.execute_block(body, Rc::new(RefCell::new(environment)));
+
+        if let Some((name, catch_body)) = catch {
+            result = match result {
+                Err(error) if error.is_catchable() => {
+                    let mut environment = Environment::new_enclosed(Rc::clone(&self.environment));
+                    let error = self.error_value(error);
+                    environment
+                        .define(name.to_string(), error)
+                        .expect("the caught error is defined into an empty scope");
+                    self.execute_block(catch_body, Rc::new(RefCell::new(environment)))
+                }
+                result => result,
+            };
+        }
+
+        //finally always runs, and an error or return from it replaces whatever was pending
+        if let Some(finally) = finally {
+            let environment = Environment::new_enclosed(Rc::clone(&self.environment));
+            self.execute_block(finally, Rc::new(RefCell::new(environment)))?;
+        }
+        result
+    }
+
+    //restores the previous scope even when a statement in the block errors
+    pub fn execute_block(
+        &mut self,
+        statements: &Vec<Stmt>,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<(), RunTimeError> {
+        let previous = std::mem::replace(&mut self.environment, environment);
+
+        let mut result = Ok(());
+        for statement in statements {
+            result = self.execute(statement);
+            if result.is_err() {
+                break;
+            }
+        }
+
+        self.environment = previous;
+        result
+    }
+
     fn is_truthy(&mut self, val: Value) -> bool {
         match val {
             Value::Null => false,
             Value::Boolean(b) => b,
+            Value::Integer(0) => false,
             Value::Number(0.0) => false,
             _ => true,
         }
     }
 }
 
-pub fn interpret(statements: Vec<Stmt>) -> Result<(), RunTimeError> {
+//written in the language itself so Error(message) builds the same kind of instance a caught
+//runtime error becomes
+const ERROR_CLASS: &str = "class Error { init(message) { this.message = message; } }";
+
+fn error_class(locals: &mut HashMap<usize, usize>) -> Rc<Class> {
+    let tokens = lex_program(ERROR_CLASS).expect("the Error class lexes");
+    let statements = parse_tokens(&tokens).expect("the Error class parses");
+    locals.extend(resolve(&statements, None).expect("the Error class resolves"));
+
+    let methods: &Vec<Rc<FunctionDecl>> = match statements.first() {
+        Some(Stmt::Class { methods, .. }) => methods,
+        _ => unreachable!("the Error class is a single class declaration"),
+    };
+    let closure = Rc::new(RefCell::new(Environment::new()));
+    let mut class_methods: HashMap<String, Rc<Function>> = HashMap::new();
+    for method in methods {
+        let function = Function::new(
+            Rc::clone(method),
+            Rc::clone(&closure),
+            method.name == "init",
+        );
+        class_methods.insert(method.name.to_string(), Rc::new(function));
+    }
+    Rc::new(Class::new("Error".to_string(), None, class_methods))
+}
+
+//every file's global scope starts out with these
+fn define_builtins(environment: &mut Environment, error_class: &Rc<Class>) {
+    environment
+        .define("Error".to_string(), Value::Class(Rc::clone(error_class)))
+        .expect("builtins are defined into an empty scope");
+}
+
+//path is the file being run, imports are resolved relative to it
+pub fn interpret(
+    statements: Vec<Stmt>,
+    locals: HashMap<usize, usize>,
+    path: Option<&Path>,
+) -> Result<(), RunTimeError> {
     let environment: Environment = Environment::new();
-    let mut interpreter: Interpreter = Interpreter::new(environment);
+    let mut interpreter: Interpreter = Interpreter::new(environment, locals);
+    if let Some(path) = path.and_then(|path| fs::canonicalize(path).ok()) {
+        interpreter.module_stack.push(path);
+    }
 
     for statement in statements.iter() {
-        match interpreter.execute(statement) {
-            Ok(_) => (),
-            Err(e) => return Err(e),
-        }
+        interpreter.execute(statement)?;
     }
     Ok(())
 }
@@ -1,2 +1,5 @@
+pub mod callable;
+pub mod class;
 pub mod interpreter;
+pub mod module;
 pub mod value;
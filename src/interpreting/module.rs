@@ -0,0 +1,38 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    environment::environment::Environment, errors::runtime_error::RunTimeError,
+    interpreting::value::Value,
+};
+
+//the namespace an imported file is bound to, its exports are the live top-level bindings
+pub struct Module {
+    pub(crate) path: String,
+    pub(crate) environment: Rc<RefCell<Environment>>,
+}
+
+impl Module {
+    pub(crate) fn new(path: String, environment: Rc<RefCell<Environment>>) -> Self {
+        Module { path, environment }
+    }
+
+    pub(crate) fn get(&self, name: &str, line: usize) -> Result<Value, RunTimeError> {
+        self.environment
+            .borrow_mut()
+            .get(name.to_string())
+            .map_err(|_| RunTimeError::UndefinedProperty {
+                name: name.to_string(),
+                line,
+            })
+    }
+
+    pub(crate) fn is_constant(&self, name: &str) -> bool {
+        self.environment.borrow().is_constant(name)
+    }
+}
+
+impl std::fmt::Debug for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<module {}>", self.path)
+    }
+}
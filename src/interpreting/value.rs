@@ -1,22 +1,130 @@
-use std::fmt::Display;
+use std::{cell::RefCell, cmp::Ordering, collections::HashMap, rc::Rc};
+
+use crate::interpreting::{
+    callable::Function,
+    class::{Class, Instance},
+    module::Module,
+};
 
 #[derive(Debug, Clone)]
 pub enum Value {
+    Integer(i64),
     Number(f64),
     String(String),
     Boolean(bool),
+    Function(Rc<Function>),
+    Class(Rc<Class>),
+    Instance(Rc<RefCell<Instance>>),
+    //shared so every variable holding the list sees the same mutations
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<HashMap<MapKey, Value>>>),
+    Module(Rc<Module>),
     Null,
 }
 
+//the subset of values that can be hashed, numbers are keyed by their bit pattern
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    Integer(i64),
+    Number(u64),
+    String(String),
+    Boolean(bool),
+}
+
 impl std::fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Value::Integer(i) => write!(f, "{}", i),
+            //whole floats keep a .0 so they can't be mistaken for integers
+            Value::Number(n) if n.is_finite() && n.fract() == 0.0 => write!(f, "{}.0", n),
             Value::Number(n) => write!(f, "{}", n),
             Value::String(s) => write!(f, "{}", s),
             Value::Boolean(b) => write!(f, "{}", b),
+            Value::Function(func) => write!(f, "<fn {}>", func.declaration.name),
+            Value::Class(class) => write!(f, "{}", class.name),
+            Value::Instance(instance) => write!(f, "{} instance", instance.borrow().class.name),
+            Value::List(elements) => {
+                let elements: Vec<String> =
+                    elements.borrow().iter().map(|e| e.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Value::Map(entries) => {
+                //sorted so printing a map is stable between runs
+                let mut entries: Vec<String> = entries
+                    .borrow()
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", k, v))
+                    .collect();
+                entries.sort();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            Value::Module(module) => write!(f, "<module {}>", module.path),
             Value::Null => write!(f, "null"),
         }
     }
 }
 
-impl Value {}
+//2^63 is exact as a float, and any float in [-2^63, 2^63) truncates to an i64 without loss
+fn integer_float_cmp(i: i64, n: f64) -> Option<Ordering> {
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if n.is_nan() {
+        None
+    } else if n >= LIMIT {
+        Some(Ordering::Less)
+    } else if n < -LIMIT {
+        Some(Ordering::Greater)
+    } else {
+        let whole: i64 = n.trunc() as i64;
+        Some(i.cmp(&whole).then(0.0_f64.partial_cmp(&n.fract())?))
+    }
+}
+
+impl std::fmt::Display for MapKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapKey::Integer(i) => write!(f, "{}", i),
+            MapKey::Number(bits) => write!(f, "{}", f64::from_bits(*bits)),
+            MapKey::String(s) => write!(f, "{}", s),
+            MapKey::Boolean(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+impl Value {
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    //None unless both are numbers, or when either is NaN. an integer is compared against a float
+    //exactly instead of being rounded to the nearest float first
+    pub fn numeric_cmp(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Integer(i1), Value::Integer(i2)) => Some(i1.cmp(i2)),
+            (Value::Number(n1), Value::Number(n2)) => n1.partial_cmp(n2),
+            (Value::Integer(i), Value::Number(n)) => integer_float_cmp(*i, *n),
+            (Value::Number(n), Value::Integer(i)) => {
+                integer_float_cmp(*i, *n).map(Ordering::reverse)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_map_key(&self) -> Option<MapKey> {
+        match self {
+            Value::Integer(i) => Some(MapKey::Integer(*i)),
+            //whole floats share a key with the equal integer, which also folds -0.0 into 0
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+                Some(MapKey::Integer(*n as i64))
+            }
+            Value::Number(n) if n.is_nan() => None,
+            Value::Number(n) => Some(MapKey::Number(n.to_bits())),
+            Value::String(s) => Some(MapKey::String(s.to_string())),
+            Value::Boolean(b) => Some(MapKey::Boolean(*b)),
+            _ => None,
+        }
+    }
+}
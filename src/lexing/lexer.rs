@@ -9,40 +9,54 @@ use std::collections::HashMap;
 static KEYWORDS: Lazy<HashMap<&'static str, TokenKind>> = Lazy::new(|| {
     let mut m = HashMap::new();
     m.insert("and", TokenKind::And);
+    m.insert("break", TokenKind::Break);
+    m.insert("catch", TokenKind::Catch);
     m.insert("class", TokenKind::Class);
+    m.insert("const", TokenKind::Const);
+    m.insert("continue", TokenKind::Continue);
     m.insert("else", TokenKind::Else);
     m.insert("false", TokenKind::False);
+    m.insert("finally", TokenKind::Finally);
     m.insert("for", TokenKind::For);
     m.insert("fun", TokenKind::Fun);
     m.insert("if", TokenKind::If);
+    m.insert("import", TokenKind::Import);
+    m.insert("let", TokenKind::Let);
+    m.insert("match", TokenKind::Match);
     m.insert("null", TokenKind::Null);
     m.insert("or", TokenKind::Or);
     m.insert("print", TokenKind::Print);
     m.insert("return", TokenKind::Return);
     m.insert("super", TokenKind::Super);
     m.insert("this", TokenKind::This);
+    m.insert("throw", TokenKind::Throw);
     m.insert("true", TokenKind::True);
+    m.insert("try", TokenKind::Try);
     m.insert("var", TokenKind::Var);
     m.insert("while", TokenKind::While);
     m
 });
 
-struct Lexer<'a> {
-    source: &'a str,
+//source is held as unicode scalar values so indexing never splits a multi-byte character
+struct Lexer {
+    source: Vec<char>,
     tokens: Vec<Token>,
     start: usize,
     current: usize,
     line: usize,
+    //one entry per open ${ holding how many plain { are nested inside it
+    interpolations: Vec<usize>,
 }
 
-impl<'a> Lexer<'a> {
-    pub fn new(source: &'a str) -> Self {
+impl Lexer {
+    pub fn new(source: &str) -> Self {
         Lexer {
-            source,
+            source: source.chars().collect(),
             tokens: Vec::new(),
             start: 0,
             current: 0,
             line: 1,
+            interpolations: Vec::new(),
         }
     }
 
@@ -51,21 +65,27 @@ impl<'a> Lexer<'a> {
     }
 
     fn advance(&mut self) -> char {
-        let c = self.source.as_bytes()[self.current] as char;
+        let c = self.source[self.current];
         self.current += 1;
         c
     }
 
     fn peek(&mut self) -> char {
-        let c = self.source.as_bytes()[self.current] as char;
-        c
+        if self.is_at_end() {
+            return '\0';
+        }
+        self.source[self.current]
     }
 
     fn peek_next(&mut self) -> char {
         if self.current + 1 >= self.source.len() {
             return '\0';
         }
-        self.source.as_bytes()[self.current + 1] as char
+        self.source[self.current + 1]
+    }
+
+    fn text(&self, start: usize, end: usize) -> String {
+        self.source[start..end].iter().collect()
     }
 
     fn match_char(&mut self, c: char) -> bool {
@@ -79,7 +99,7 @@ impl<'a> Lexer<'a> {
     }
 
     fn add_token(&mut self, kind: TokenKind, literal: Option<Literal>) {
-        let lexeme = self.source[self.start..self.current].to_string();
+        let lexeme = self.text(self.start, self.current);
 
         self.tokens.push(Token {
             kind,
@@ -93,12 +113,85 @@ impl<'a> Lexer<'a> {
         self.line += 1
     }
 
+    //called with the backslash already consumed
+    fn escape(&mut self) -> Result<char, LexError> {
+        if self.is_at_end() {
+            return Err(LexError::UnterminatedString { line: self.line });
+        }
+
+        match self.advance() {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            '0' => Ok('\0'),
+            '"' => Ok('"'),
+            '\\' => Ok('\\'),
+            '$' => Ok('$'),
+            'u' => {
+                let escape_start = self.current - 2;
+                if self.peek() != '{' {
+                    return Err(LexError::InvalidEscape {
+                        sequence: self.text(escape_start, self.current),
+                        line: self.line,
+                    });
+                }
+                self.advance();
+                while self.peek().is_ascii_hexdigit() {
+                    self.advance();
+                }
+                if self.peek() != '}' {
+                    return Err(LexError::InvalidEscape {
+                        sequence: self.text(escape_start, self.current),
+                        line: self.line,
+                    });
+                }
+                self.advance();
+
+                let digits = self.text(escape_start + 3, self.current - 1);
+                match u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                {
+                    Some(c) => Ok(c),
+                    None => Err(LexError::InvalidEscape {
+                        sequence: self.text(escape_start, self.current),
+                        line: self.line,
+                    }),
+                }
+            }
+            c => Err(LexError::InvalidEscape {
+                sequence: format!("\\{}", c),
+                line: self.line,
+            }),
+        }
+    }
+
+    //called after the opening " or after the } closing an interpolation. a ${ ends the current
+    //segment as an Interpolation token and hands back to scan_token for the embedded expression
     fn string(&mut self) -> Result<(), LexError> {
-        while self.peek() != '"' && !self.is_at_end() {
-            if self.peek() == '\n' {
-                self.increment_line()
-            };
-            self.advance();
+        let mut value = String::new();
+
+        while !self.is_at_end() && self.peek() != '"' {
+            if self.peek() == '$' && self.peek_next() == '{' {
+                self.advance();
+                self.advance();
+                self.add_token(
+                    TokenKind::Interpolation,
+                    Some(Literal::StringLiteral(value)),
+                );
+                self.interpolations.push(0);
+                return Ok(());
+            }
+
+            let c = self.advance();
+            match c {
+                '\\' => value.push(self.escape()?),
+                '\n' => {
+                    self.increment_line();
+                    value.push(c)
+                }
+                _ => value.push(c),
+            }
         }
 
         if self.is_at_end() {
@@ -107,11 +200,9 @@ impl<'a> Lexer<'a> {
 
         self.advance();
 
-        let value = &self.source[self.start + 1..self.current - 1]; //note this only works if all values are ASCII, which is assumed. Panics otherwise
-
         self.add_token(
             TokenKind::StringLiteral,
-            Some(Literal::StringLiteral(value.to_string())),
+            Some(Literal::StringLiteral(value)),
         );
         Ok(())
     }
@@ -121,35 +212,41 @@ impl<'a> Lexer<'a> {
             self.advance();
         }
 
+        //a fractional part is what makes the literal a float rather than an integer
+        let mut is_float = false;
         if self.peek() == '.' && is_digit(self.peek_next()) {
+            is_float = true;
             self.advance();
             while is_digit(self.peek()) {
                 self.advance();
             }
         }
 
-        let num_str = &self.source[self.start..self.current];
-        let parsed_num: Result<f64, LexError> = match num_str.parse() {
-            Ok(num) => Ok(num),
-            Err(_) => Err(LexError::NumberParsingError {
-                num_str: num_str.to_string(),
-                line: self.line,
-            }),
+        let num_str = self.text(self.start, self.current);
+        let parsed_num: Option<Literal> = if is_float {
+            num_str.parse().ok().map(Literal::Number)
+        } else {
+            num_str.parse().ok().map(Literal::Integer)
         };
         match parsed_num {
-            Ok(val) => self.add_token(TokenKind::Number, Some(Literal::Number(val))),
-            Err(e) => return Err(e),
+            Some(val) => self.add_token(TokenKind::Number, Some(val)),
+            None => {
+                return Err(LexError::NumberParsingError {
+                    num_str,
+                    line: self.line,
+                });
+            }
         };
         Ok(())
     }
 
-    fn identifier(&mut self) -> () {
+    fn identifier(&mut self) {
         while is_alphanumeric(self.peek()) {
             self.advance();
         }
 
-        let text = &self.source[self.start..self.current];
-        let kind = match KEYWORDS.get(text) {
+        let text = self.text(self.start, self.current);
+        let kind = match KEYWORDS.get(text.as_str()) {
             Some(kind) => kind.clone(),
             None => TokenKind::Identifier,
         };
@@ -164,42 +261,117 @@ impl<'a> Lexer<'a> {
             '\n' => self.increment_line(),
             '(' => self.add_token(TokenKind::LeftParen, None),
             ')' => self.add_token(TokenKind::RightParen, None),
-            '{' => self.add_token(TokenKind::LeftBrace, None),
-            '}' => self.add_token(TokenKind::RightBrace, None),
-            '+' => self.add_token(TokenKind::Plus, None),
-            '-' => self.add_token(TokenKind::Minus, None),
-            '*' => self.add_token(TokenKind::Star, None),
-            '.' => self.add_token(TokenKind::Dot, None),
+            '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                self.add_token(TokenKind::LeftBrace, None)
+            }
+            '}' => match self.interpolations.last_mut() {
+                Some(0) => {
+                    self.interpolations.pop();
+                    self.string()?
+                }
+                Some(depth) => {
+                    *depth -= 1;
+                    self.add_token(TokenKind::RightBrace, None)
+                }
+                None => self.add_token(TokenKind::RightBrace, None),
+            },
+            '[' => self.add_token(TokenKind::LeftBracket, None),
+            ']' => self.add_token(TokenKind::RightBracket, None),
+            '+' => {
+                if self.match_char('=') {
+                    self.advance();
+                    self.add_token(TokenKind::PlusEqual, None);
+                } else if self.match_char('+') {
+                    self.advance();
+                    self.add_token(TokenKind::PlusPlus, None);
+                } else {
+                    self.add_token(TokenKind::Plus, None);
+                }
+            }
+            '-' => {
+                if self.match_char('=') {
+                    self.advance();
+                    self.add_token(TokenKind::MinusEqual, None);
+                } else if self.match_char('-') {
+                    self.advance();
+                    self.add_token(TokenKind::MinusMinus, None);
+                } else {
+                    self.add_token(TokenKind::Minus, None);
+                }
+            }
+            '*' => {
+                if self.match_char('*') {
+                    self.advance();
+                    self.add_token(TokenKind::StarStar, None);
+                } else if self.match_char('=') {
+                    self.advance();
+                    self.add_token(TokenKind::StarEqual, None);
+                } else {
+                    self.add_token(TokenKind::Star, None);
+                }
+            }
+            '%' => self.add_token(TokenKind::Percent, None),
+            '&' => self.add_token(TokenKind::Ampersand, None),
+            '|' => self.add_token(TokenKind::Pipe, None),
+            '^' => self.add_token(TokenKind::Caret, None),
+            '~' => self.add_token(TokenKind::Tilde, None),
+            '.' => {
+                if self.match_char('.') {
+                    self.advance();
+                    if self.match_char('=') {
+                        self.advance();
+                        self.add_token(TokenKind::DotDotEqual, None);
+                    } else {
+                        self.add_token(TokenKind::DotDot, None);
+                    }
+                } else {
+                    self.add_token(TokenKind::Dot, None);
+                }
+            }
             ';' => self.add_token(TokenKind::Semicolon, None),
             ',' => self.add_token(TokenKind::Comma, None),
+            ':' => self.add_token(TokenKind::Colon, None),
+            '?' => self.add_token(TokenKind::Question, None),
             '!' => {
                 if self.match_char('=') {
-                    self.add_token(TokenKind::BangEqual, None);
                     self.advance();
+                    self.add_token(TokenKind::BangEqual, None);
                 } else {
                     self.add_token(TokenKind::Bang, None);
                 }
             }
             '=' => {
                 if self.match_char('=') {
+                    self.advance();
                     self.add_token(TokenKind::EqualEqual, None);
+                } else if self.match_char('>') {
                     self.advance();
+                    self.add_token(TokenKind::Arrow, None);
                 } else {
                     self.add_token(TokenKind::Equal, None);
                 }
             }
             '<' => {
                 if self.match_char('=') {
+                    self.advance();
                     self.add_token(TokenKind::LessEqual, None);
+                } else if self.match_char('<') {
                     self.advance();
+                    self.add_token(TokenKind::LessLess, None);
                 } else {
                     self.add_token(TokenKind::LessThan, None);
                 }
             }
             '>' => {
                 if self.match_char('=') {
+                    self.advance();
                     self.add_token(TokenKind::GreaterEqual, None);
+                } else if self.match_char('>') {
                     self.advance();
+                    self.add_token(TokenKind::GreaterGreater, None);
                 } else {
                     self.add_token(TokenKind::GreaterThan, None);
                 }
@@ -209,6 +381,9 @@ impl<'a> Lexer<'a> {
                     while self.peek() != '\n' && !self.is_at_end() {
                         self.advance();
                     }
+                } else if self.match_char('=') {
+                    self.advance();
+                    self.add_token(TokenKind::SlashEqual, None);
                 } else {
                     self.add_token(TokenKind::Slash, None);
                 }
@@ -232,6 +407,8 @@ impl<'a> Lexer<'a> {
                     }
                 } else if is_alpha(c) {
                     self.identifier();
+                } else if c.is_whitespace() {
+                    //covers unicode spacing like non-breaking spaces
                 } else {
                     return Err(LexError::UnexpectedCharacter {
                         char: c,
@@ -245,15 +422,16 @@ impl<'a> Lexer<'a> {
 }
 
 fn is_digit(c: char) -> bool {
-    return c >= '0' && c <= '9';
+    c.is_ascii_digit()
 }
 
+//identifiers may use any unicode letter, but numbers stay ascii only
 fn is_alpha(c: char) -> bool {
-    return (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || (c == '_');
+    c.is_alphabetic() || c == '_'
 }
 
 fn is_alphanumeric(c: char) -> bool {
-    return is_alpha(c) || is_digit(c);
+    is_alpha(c) || c.is_numeric()
 }
 
 pub fn lex_program(source: &str) -> Result<Vec<Token>, LexError> {
@@ -264,8 +442,12 @@ pub fn lex_program(source: &str) -> Result<Vec<Token>, LexError> {
         lexer.scan_token()?;
     }
 
+    if !lexer.interpolations.is_empty() {
+        return Err(LexError::UnterminatedString { line: lexer.line });
+    }
+
     lexer.tokens.push(Token {
-        kind: TokenKind::EOF,
+        kind: TokenKind::Eof,
         lexeme: "".to_string(),
         literal: None,
         line: lexer.line,
@@ -1,4 +1,4 @@
-use crate::{errors::lex_error::LexError, parsing::ast::Literal};
+use crate::parsing::ast::Literal;
 #[derive(Clone, Debug)]
 pub struct Token {
     pub kind: TokenKind,
@@ -23,25 +23,49 @@ pub enum TokenKind {
     Super,
     Return,
     This,
+    Break,
+    Continue,
+    Match,
+    Throw,
+    Try,
+    Catch,
+    Finally,
+    Import,
 
     //literal
     Number,
     StringLiteral,
+    Interpolation, //string segment that comes right before a ${
     True,
     False,
 
     //identifiers
     Identifier,
     Var,
+    Const,
+    Let,
 
     //operators
     Plus,
     Minus,
     Dot, //property calls
+    DotDot,
+    DotDotEqual,
     Slash,
     Star,
+    StarStar,
+    Percent,
     Equal,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PlusPlus,
+    MinusMinus,
     Comma,
+    Colon,
+    Question,
+    Arrow,
 
     //punctuation
     Semicolon,
@@ -49,6 +73,8 @@ pub enum TokenKind {
     RightParen,
     RightBrace,
     LeftBrace,
+    LeftBracket,
+    RightBracket,
 
     //logical operator
     GreaterThan,
@@ -59,6 +85,14 @@ pub enum TokenKind {
     BangEqual,
     EqualEqual,
 
-    EOF,
+    //bitwise operator
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
+    LessLess,
+    GreaterGreater,
+
+    Eof,
 }
 impl Token {}
@@ -1,36 +1,51 @@
+mod checking;
 mod environment;
 mod errors;
 mod interpreting;
 mod lexing;
 mod parsing;
+mod resolving;
 
 use lexing::lexer::lex_program;
 use lexing::token::Token;
 use std::env;
 use std::fs;
+use std::path::Path;
 
+use crate::checking::checker::check;
+use crate::checking::inference::infer;
 use crate::errors::interpreter_error::InterpreterError;
 use crate::interpreting::interpreter::interpret;
-use crate::interpreting::value::Value;
-use crate::parsing::ast::Expr;
 use crate::parsing::ast::Stmt;
 use crate::parsing::parser::parse_tokens;
+use crate::resolving::resolver::resolve;
 
 pub fn error(e: InterpreterError) {
     println!("{}", e);
 }
 
-fn process_args() -> Option<String> {
-    let args: Vec<String> = env::args().collect();
+//the program file if one was given, and whether --typed was passed
+fn process_args() -> (Option<String>, bool) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let typed: bool = args.iter().any(|arg| arg == "--typed");
+    let args: Vec<String> = args.into_iter().filter(|arg| arg != "--typed").collect();
     let mut file_path: Option<String> = None;
-    if args.len() == 2 {
-        file_path = Some(args[1].clone());
+    if args.len() == 1 {
+        file_path = Some(args[0].clone());
     }
-    return file_path;
+    (file_path, typed)
+}
+
+//prints the inferred type of each top level binding, stopping at the first unification error
+fn infer_types(statements: &Vec<Stmt>) -> Result<(), InterpreterError> {
+    for (name, signature) in infer(statements)? {
+        println!("{}: {}", name, signature);
+    }
+    Ok(())
 }
 
 //file input
-fn run_file(program_file: &str) -> Result<(), InterpreterError> {
+fn run_file(program_file: &str, typed: bool) -> Result<(), InterpreterError> {
     let bytes = fs::read(program_file);
 
     match bytes {
@@ -46,7 +61,12 @@ fn run_file(program_file: &str) -> Result<(), InterpreterError> {
                 println!("{}", statement)
             }
 
-            interpret(statements)?;
+            let locals = resolve(&statements, Some(Path::new(program_file)))?;
+            check(&statements)?;
+            if typed {
+                infer_types(&statements)?;
+            }
+            interpret(statements, locals, Some(Path::new(program_file)))?;
         }
         Err(e) => {
             println!("Error: {e}");
@@ -56,20 +76,14 @@ fn run_file(program_file: &str) -> Result<(), InterpreterError> {
 }
 
 //CLI listening
-fn run_prompt() -> Result<(), InterpreterError> {
+fn run_prompt(typed: bool) -> Result<(), InterpreterError> {
     let mut input = String::new();
 
     std::io::stdin()
         .read_line(&mut input)
         .expect("Failed to read line");
 
-    let tokens: Vec<Token> = match lex_program(&input) {
-        Ok(tokens) => tokens,
-        Err(e) => {
-            error(InterpreterError::LexError(e));
-            Vec::new()
-        }
-    };
+    let tokens: Vec<Token> = lex_program(&input)?;
 
     for token in tokens.iter() {
         println!("{:?}", token);
@@ -81,19 +95,24 @@ fn run_prompt() -> Result<(), InterpreterError> {
         println!("{}", statement)
     }
 
-    interpret(statements)?;
+    let locals = resolve(&statements, None)?;
+    check(&statements)?;
+    if typed {
+        infer_types(&statements)?;
+    }
+    interpret(statements, locals, None)?;
 
     Ok(())
 }
 
 fn main() {
-    let program_file: Option<String> = process_args();
+    let (program_file, typed) = process_args();
     match program_file {
-        Some(p) => match run_file(&p) {
+        Some(p) => match run_file(&p, typed) {
             Ok(_) => (),
             Err(e) => error(e),
         },
-        None => match run_prompt() {
+        None => match run_prompt(typed) {
             Ok(_) => (),
             Err(e) => error(e),
         },
@@ -1,13 +1,93 @@
-use std::fmt::write;
+use std::rc::Rc;
 
 #[derive(Debug)]
 pub enum Stmt {
     Print(Expr),
     Expression(Expr),
+    //const and let declarations are not mutable and always have an initializer
     Var {
         name: String,
         initializer: Option<Expr>,
+        mutable: bool,
+        annotation: Option<TypeAnnotation>,
+        line: usize,
     },
+    Block(Vec<Stmt>),
+    If {
+        condition: Expr,
+        then_branch: Box<Stmt>,
+        else_branch: Option<Box<Stmt>>,
+    },
+    While {
+        condition: Expr,
+        body: Box<Stmt>,
+        //only set by desugared for loops, runs after the body even when it continues
+        increment: Option<Expr>,
+        label: Option<String>,
+    },
+    Break {
+        label: Option<String>,
+    },
+    Continue {
+        label: Option<String>,
+    },
+    Function(Rc<FunctionDecl>),
+    Return {
+        value: Option<Expr>,
+        line: usize,
+    },
+    Class {
+        name: String,
+        superclass: Option<Expr>,
+        methods: Vec<Rc<FunctionDecl>>,
+        line: usize,
+    },
+    Throw {
+        value: Expr,
+        line: usize,
+    },
+    //import "path" as alias binds the module, import { names } from "path" binds each name
+    Import {
+        path: String,
+        alias: Option<String>,
+        names: Vec<String>,
+        line: usize,
+    },
+    //at least one of catch and finally is present, catch holds the name the error is bound to
+    Try {
+        body: Vec<Stmt>,
+        catch: Option<(String, Vec<Stmt>)>,
+        finally: Option<Vec<Stmt>>,
+    },
+}
+
+//shared with every function value created from the declaration so calls don't clone the body
+#[derive(Debug)]
+pub struct FunctionDecl {
+    pub name: String,
+    pub params: Vec<String>,
+    //one entry per param, None where the param is unannotated
+    pub param_types: Vec<Option<TypeAnnotation>>,
+    pub return_type: Option<TypeAnnotation>,
+    pub body: Vec<Stmt>,
+    pub line: usize,
+}
+
+//written after a colon on variables, params and function return types. anything that isn't a
+//builtin name is taken to be a class
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeAnnotation {
+    Integer,
+    Float,
+    Number,
+    String,
+    Boolean,
+    Null,
+    List,
+    Map,
+    Function,
+    Any,
+    Class(String),
 }
 
 #[derive(Debug, Clone)]
@@ -17,16 +97,139 @@ pub enum Expr {
         left: Box<Expr>,
         op: BinaryOp,
         right: Box<Expr>,
+        line: usize,
     },
     Unary {
         op: UnaryOp,
         right: Box<Expr>,
+        line: usize,
+    },
+    Logical {
+        left: Box<Expr>,
+        op: BinaryOp,
+        right: Box<Expr>,
+    },
+    //arms are tried in order, each with its own scope for the bindings of its pattern
+    Match {
+        subject: Box<Expr>,
+        arms: Vec<MatchArm>,
+        line: usize,
+    },
+    //anonymous function, the arrow form's body is a single return of its expression
+    Lambda(Rc<FunctionDecl>),
+    //only the selected branch is evaluated
+    Conditional {
+        condition: Box<Expr>,
+        then_branch: Box<Expr>,
+        else_branch: Box<Expr>,
     },
     Grouping {
         exp: Box<Expr>,
     },
     Literal(Literal),
-    Variable(String),
+    //id is unique per node so the resolver can record the scope depth of each reference
+    Variable {
+        name: String,
+        id: usize,
+        line: usize,
+    },
+    Assign {
+        name: String,
+        value: Box<Expr>,
+        id: usize,
+        line: usize,
+    },
+    Call {
+        callee: Box<Expr>,
+        arguments: Vec<Expr>,
+        line: usize,
+    },
+    Get {
+        object: Box<Expr>,
+        name: String,
+        line: usize,
+    },
+    Set {
+        object: Box<Expr>,
+        name: String,
+        value: Box<Expr>,
+        line: usize,
+    },
+    This {
+        id: usize,
+        line: usize,
+    },
+    //the parts are concatenated using each value's Display, so non strings convert implicitly
+    Interpolation(Vec<Expr>),
+    List(Vec<Expr>),
+    Map {
+        entries: Vec<(Expr, Expr)>,
+        line: usize,
+    },
+    Index {
+        object: Box<Expr>,
+        index: Box<Expr>,
+        line: usize,
+    },
+    IndexSet {
+        object: Box<Expr>,
+        index: Box<Expr>,
+        value: Box<Expr>,
+        line: usize,
+    },
+    //target is a Variable, Get or Index and is only evaluated once
+    CompoundAssign {
+        target: Box<Expr>,
+        op: BinaryOp,
+        value: Box<Expr>,
+        line: usize,
+    },
+    //++ and -- with op Plus or Minus, prefix yields the updated value and postfix the old one
+    Increment {
+        target: Box<Expr>,
+        op: BinaryOp,
+        prefix: bool,
+        line: usize,
+    },
+    Super {
+        method: String,
+        id: usize,
+        line: usize,
+    },
+}
+
+//patterns only test a value and collect bindings, nothing in them is evaluated except the class
+//of an instance pattern
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Wildcard,
+    Binding(String),
+    Literal(Literal),
+    //numeric only, end is exclusive unless written with ..=
+    Range {
+        start: Literal,
+        end: Literal,
+        inclusive: bool,
+    },
+    //rest is the trailing ..name, when absent the list length must match exactly
+    List {
+        elements: Vec<Pattern>,
+        rest: Option<Box<Pattern>>,
+    },
+    //extra keys in the matched map are ignored
+    Map(Vec<(Literal, Pattern)>),
+    Instance {
+        class: Expr,
+        fields: Vec<(String, Pattern)>,
+        line: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: Expr,
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +238,13 @@ pub enum BinaryOp {
     Minus,
     Star,
     Slash,
+    Percent,
+    StarStar,
+    Ampersand,
+    Pipe,
+    Caret,
+    LessLess,
+    GreaterGreater,
     Equal,
     GreaterEqual,
     GreaterThan,
@@ -50,10 +260,13 @@ pub enum BinaryOp {
 pub enum UnaryOp {
     Bang,
     Minus,
+    Tilde,
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum Literal {
+    Integer(i64),
     Number(f64),
     StringLiteral(String),
     Null,
@@ -68,10 +281,114 @@ impl std::fmt::Display for Stmt {
         match self {
             Stmt::Expression(e) => write!(f, "{}", e), //normally don't display anything but nice for testing
             Stmt::Print(e) => write!(f, "{}", e),
-            Stmt::Var { name, initializer } => match initializer {
+            Stmt::Var {
+                name,
+                initializer,
+                mutable: false,
+                ..
+            } => match initializer {
+                Some(initializer) => write!(f, "constant {} with value {}", name, initializer),
+                None => write!(f, "constant {} with no assigned value", name),
+            },
+            Stmt::Var {
+                name, initializer, ..
+            } => match initializer {
                 Some(initializer) => write!(f, "variable {} with value {}", name, initializer),
                 None => write!(f, "variable {} with no assigned value", name),
             },
+            Stmt::Block(statements) => {
+                writeln!(f, "{{")?;
+                for statement in statements {
+                    writeln!(f, "{}", statement)?;
+                }
+                write!(f, "}}")
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => match else_branch {
+                Some(else_branch) => write!(
+                    f,
+                    "if {} then {} else {}",
+                    condition, then_branch, else_branch
+                ),
+                None => write!(f, "if {} then {}", condition, then_branch),
+            },
+            Stmt::While {
+                condition,
+                body,
+                increment,
+                label,
+            } => {
+                if let Some(label) = label {
+                    write!(f, "{}: ", label)?;
+                }
+                write!(f, "while {} do {}", condition, body)?;
+                match increment {
+                    Some(increment) => write!(f, " then {}", increment),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Break { label } => match label {
+                Some(label) => write!(f, "break {}", label),
+                None => write!(f, "break"),
+            },
+            Stmt::Continue { label } => match label {
+                Some(label) => write!(f, "continue {}", label),
+                None => write!(f, "continue"),
+            },
+            Stmt::Function(declaration) => {
+                write!(
+                    f,
+                    "fun {}({})",
+                    declaration.name,
+                    declaration.params.join(", ")
+                )
+            }
+            Stmt::Throw { value, .. } => write!(f, "throw {}", value),
+            Stmt::Import {
+                path, alias, names, ..
+            } => match alias {
+                Some(alias) => write!(f, "import {} as {}", path, alias),
+                None => write!(f, "import {{ {} }} from {}", names.join(", "), path),
+            },
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                write!(f, "try {{ {} statements }}", body.len())?;
+                if let Some((name, catch_body)) = catch {
+                    write!(f, " catch ({}) {{ {} statements }}", name, catch_body.len())?;
+                }
+                match finally {
+                    Some(finally) => write!(f, " finally {{ {} statements }}", finally.len()),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Return { value, .. } => match value {
+                Some(value) => write!(f, "return {}", value),
+                None => write!(f, "return"),
+            },
+            Stmt::Class {
+                name,
+                superclass,
+                methods,
+                ..
+            } => {
+                let methods: Vec<String> = methods.iter().map(|m| m.name.to_string()).collect();
+                match superclass {
+                    Some(superclass) => write!(
+                        f,
+                        "class {} < {} {{ {} }}",
+                        name,
+                        superclass,
+                        methods.join(", ")
+                    ),
+                    None => write!(f, "class {} {{ {} }}", name, methods.join(", ")),
+                }
+            }
         }
     }
 }
@@ -79,10 +396,15 @@ impl std::fmt::Display for Stmt {
 impl std::fmt::Display for Expr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Expr::Binary { left, op, right } => {
+            Expr::Binary {
+                left, op, right, ..
+            } => {
+                write!(f, "({}{}{})", left, op, right)
+            }
+            Expr::Logical { left, op, right } => {
                 write!(f, "({}{}{})", left, op, right)
             }
-            Expr::Unary { op, right } => {
+            Expr::Unary { op, right, .. } => {
                 write!(f, "({}{})", op, right)
             }
             Expr::Grouping { exp } => {
@@ -91,8 +413,88 @@ impl std::fmt::Display for Expr {
             Expr::Literal(val) => {
                 write!(f, "{}", val)
             }
-            Expr::Variable(t) => {
-                write!(f, "{}", t)
+            Expr::Variable { name, .. } => {
+                write!(f, "{}", name)
+            }
+            Expr::Match { subject, arms, .. } => {
+                let arms: Vec<String> = arms.iter().map(|a| a.to_string()).collect();
+                write!(f, "match {} {{ {} }}", subject, arms.join(", "))
+            }
+            Expr::Lambda(declaration) => {
+                write!(f, "fun ({})", declaration.params.join(", "))
+            }
+            Expr::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                write!(f, "({} ? {} : {})", condition, then_branch, else_branch)
+            }
+            Expr::Assign { name, value, .. } => {
+                write!(f, "({} = {})", name, value)
+            }
+            Expr::Call {
+                callee, arguments, ..
+            } => {
+                let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}({})", callee, arguments.join(", "))
+            }
+            Expr::Get { object, name, .. } => {
+                write!(f, "{}.{}", object, name)
+            }
+            Expr::Set {
+                object,
+                name,
+                value,
+                ..
+            } => {
+                write!(f, "({}.{} = {})", object, name, value)
+            }
+            Expr::This { .. } => {
+                write!(f, "this")
+            }
+            Expr::Super { method, .. } => {
+                write!(f, "super.{}", method)
+            }
+            Expr::Interpolation(parts) => {
+                let parts: Vec<String> = parts.iter().map(|p| p.to_string()).collect();
+                write!(f, "(interpolate {})", parts.join(" "))
+            }
+            Expr::List(elements) => {
+                let elements: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Expr::Map { entries, .. } => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", k, v))
+                    .collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            Expr::Index { object, index, .. } => {
+                write!(f, "{}[{}]", object, index)
+            }
+            Expr::IndexSet {
+                object,
+                index,
+                value,
+                ..
+            } => {
+                write!(f, "({}[{}] = {})", object, index, value)
+            }
+            Expr::CompoundAssign {
+                target, op, value, ..
+            } => {
+                write!(f, "({} {}= {})", target, op, value)
+            }
+            Expr::Increment {
+                target, op, prefix, ..
+            } => {
+                if *prefix {
+                    write!(f, "({}{}{})", op, op, target)
+                } else {
+                    write!(f, "({}{}{})", target, op, op)
+                }
             }
         }
     }
@@ -105,6 +507,13 @@ impl std::fmt::Display for BinaryOp {
             BinaryOp::Minus => "-",
             BinaryOp::Star => "*",
             BinaryOp::Slash => "/",
+            BinaryOp::Percent => "%",
+            BinaryOp::StarStar => "**",
+            BinaryOp::Ampersand => "&",
+            BinaryOp::Pipe => "|",
+            BinaryOp::Caret => "^",
+            BinaryOp::LessLess => "<<",
+            BinaryOp::GreaterGreater => ">>",
             BinaryOp::And => "&&",
             BinaryOp::Or => "||",
             BinaryOp::Equal => "=",
@@ -124,14 +533,82 @@ impl std::fmt::Display for UnaryOp {
         let s = match self {
             UnaryOp::Bang => "!",
             UnaryOp::Minus => "-",
+            UnaryOp::Tilde => "~",
         };
         write!(f, "{}", s)
     }
 }
 
+impl std::fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeAnnotation::Integer => write!(f, "int"),
+            TypeAnnotation::Float => write!(f, "float"),
+            TypeAnnotation::Number => write!(f, "number"),
+            TypeAnnotation::String => write!(f, "string"),
+            TypeAnnotation::Boolean => write!(f, "bool"),
+            TypeAnnotation::Null => write!(f, "null"),
+            TypeAnnotation::List => write!(f, "list"),
+            TypeAnnotation::Map => write!(f, "map"),
+            TypeAnnotation::Function => write!(f, "function"),
+            TypeAnnotation::Any => write!(f, "any"),
+            TypeAnnotation::Class(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Binding(name) => write!(f, "{}", name),
+            Pattern::Literal(literal) => write!(f, "{}", literal),
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let op = if *inclusive { "..=" } else { ".." };
+                write!(f, "{}{}{}", start, op, end)
+            }
+            Pattern::List { elements, rest } => {
+                let mut elements: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+                if let Some(rest) = rest {
+                    elements.push(format!("..{}", rest));
+                }
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Pattern::Map(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", k, v))
+                    .collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            Pattern::Instance { class, fields, .. } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", k, v))
+                    .collect();
+                write!(f, "{} {{{}}}", class, fields.join(", "))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.guard {
+            Some(guard) => write!(f, "{} if {} => {}", self.pattern, guard, self.body),
+            None => write!(f, "{} => {}", self.pattern, self.body),
+        }
+    }
+}
+
 impl std::fmt::Display for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
+            Literal::Integer(val) => val.to_string(),
             Literal::Number(val) => val.to_string(),
             Literal::StringLiteral(val) => val.to_string(),
             Literal::Null => "NULL".to_string(),
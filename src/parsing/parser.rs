@@ -1,19 +1,40 @@
+use std::{
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
 use crate::{
     errors::parse_error::ParseError,
     lexing::token::{Token, TokenKind},
-    parsing::ast::{BinaryOp, Expr, Literal, Stmt, UnaryOp},
+    parsing::ast::{
+        BinaryOp, Expr, FunctionDecl, Literal, MatchArm, Pattern, Stmt, TypeAnnotation, UnaryOp,
+    },
 };
 
+//global rather than per parser so ids stay unique across every file that gets parsed
+static NEXT_EXPR_ID: AtomicUsize = AtomicUsize::new(0);
+
+fn next_expr_id() -> usize {
+    NEXT_EXPR_ID.fetch_add(1, Ordering::Relaxed)
+}
+
 struct Parser<'a> {
     tokens: &'a Vec<Token>,
     current: usize,
+    //one entry per loop currently being parsed, holding its label if it has one
+    loop_labels: Vec<Option<String>>,
+    //off while parsing the top level of a match guard, where (x) => would be read as a lambda
+    //instead of the guard followed by the arm's =>
+    allow_arrow: bool,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokens: &'a Vec<Token>) -> Self {
         Parser {
-            tokens: tokens,
+            tokens,
             current: 0,
+            loop_labels: Vec::new(),
+            allow_arrow: true,
         }
     }
 
@@ -21,11 +42,15 @@ impl<'a> Parser<'a> {
         self.tokens[self.current].clone()
     }
 
-    fn is_at_end(&mut self) -> bool {
-        match self.peek().kind {
-            TokenKind::EOF => true,
-            _ => false,
+    fn peek_next(&mut self) -> Token {
+        if self.is_at_end() {
+            return self.peek();
         }
+        self.tokens[self.current + 1].clone()
+    }
+
+    fn is_at_end(&mut self) -> bool {
+        matches!(self.peek().kind, TokenKind::Eof)
     }
 
     fn previous(&mut self) -> &Token {
@@ -43,7 +68,7 @@ impl<'a> Parser<'a> {
         if self.is_at_end() {
             return false;
         }
-        return self.peek().kind == token_type;
+        self.peek().kind == token_type
     }
 
     fn match_token(&mut self, token_types: Vec<TokenKind>) -> bool {
@@ -54,23 +79,28 @@ impl<'a> Parser<'a> {
             }
         }
 
-        return false;
+        false
     }
 
-    fn synchronize(&mut self) -> () {
+    fn synchronize(&mut self) {
         self.advance();
 
         while !self.is_at_end() {
             match self.previous().kind {
-                TokenKind::Semicolon => return (),
-                TokenKind::Class => return (),
-                TokenKind::Fun => return (),
-                TokenKind::Var => return (),
-                TokenKind::For => return (),
-                TokenKind::If => return (),
-                TokenKind::While => return (),
-                TokenKind::Print => return (),
-                TokenKind::Return => return (),
+                TokenKind::Semicolon => return,
+                TokenKind::Class => return,
+                TokenKind::Fun => return,
+                TokenKind::Var => return,
+                TokenKind::Const => return,
+                TokenKind::Let => return,
+                TokenKind::For => return,
+                TokenKind::If => return,
+                TokenKind::While => return,
+                TokenKind::Print => return,
+                TokenKind::Return => return,
+                TokenKind::Throw => return,
+                TokenKind::Try => return,
+                TokenKind::Import => return,
                 _ => (),
             }
             self.advance();
@@ -84,72 +114,483 @@ impl<'a> Parser<'a> {
         Err(ParseError::InvalidGrouping(message))
     }
 
+    //checks whether the tokens after an opening ( are a parameter list followed by =>
+    fn is_arrow_function(&self) -> bool {
+        let kind_at = |position: usize| self.tokens.get(position).map(|token| &token.kind);
+        //annotations are a colon and a single type name
+        let skip_annotation = |position: usize| match kind_at(position) {
+            Some(TokenKind::Colon) => position + 2,
+            _ => position,
+        };
+
+        let mut position: usize = self.current;
+        if kind_at(position) != Some(&TokenKind::RightParen) {
+            loop {
+                if kind_at(position) != Some(&TokenKind::Identifier) {
+                    return false;
+                }
+                position = skip_annotation(position + 1);
+                if kind_at(position) != Some(&TokenKind::Comma) {
+                    break;
+                }
+                position += 1;
+            }
+        }
+        kind_at(position) == Some(&TokenKind::RightParen)
+            && kind_at(skip_annotation(position + 1)) == Some(&TokenKind::Arrow)
+    }
+
+    fn lambda(&mut self) -> Result<Expr, ParseError> {
+        let line: usize = self.previous().line;
+        self.consume(TokenKind::LeftParen, "Expect ( after fun".to_string())?;
+        let (params, param_types) = self.parameters()?;
+        let return_type: Option<TypeAnnotation> = self.optional_annotation()?;
+        let body: Vec<Stmt> = self.function_body("lambda")?;
+        Ok(Expr::Lambda(Rc::new(FunctionDecl {
+            name: "anonymous".to_string(),
+            params,
+            param_types,
+            return_type,
+            body,
+            line,
+        })))
+    }
+
+    fn arrow_function(&mut self) -> Result<Expr, ParseError> {
+        let (params, param_types) = self.parameters()?;
+        let return_type: Option<TypeAnnotation> = self.optional_annotation()?;
+        let line: usize = self
+            .consume(TokenKind::Arrow, "Expect => after parameters".to_string())?
+            .line;
+        let value: Expr = self.expression()?;
+        Ok(Expr::Lambda(Rc::new(FunctionDecl {
+            name: "anonymous".to_string(),
+            params,
+            param_types,
+            return_type,
+            body: vec![Stmt::Return {
+                value: Some(value),
+                line,
+            }],
+            line,
+        })))
+    }
+
+    //literals allowed in patterns, including negative numbers
+    fn pattern_literal(&mut self) -> Result<Literal, ParseError> {
+        let line: usize = self.peek().line;
+        let negative: bool = self.match_token(vec![TokenKind::Minus]);
+        if self.check(TokenKind::Interpolation) {
+            return Err(ParseError::InvalidPattern { line });
+        }
+        let literal: Literal =
+            parse_literal(self.peek()).map_err(|_| ParseError::InvalidPattern { line })?;
+        self.advance();
+
+        match literal {
+            Literal::Integer(i) if negative => Ok(Literal::Integer(-i)),
+            Literal::Number(n) if negative => Ok(Literal::Number(-n)),
+            _ if negative => Err(ParseError::InvalidPattern { line }),
+            literal => Ok(literal),
+        }
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, ParseError> {
+        let line: usize = self.peek().line;
+
+        if self.match_token(vec![TokenKind::LeftBracket]) {
+            let mut elements: Vec<Pattern> = Vec::new();
+            let mut rest: Option<Box<Pattern>> = None;
+            if !self.check(TokenKind::RightBracket) {
+                loop {
+                    if self.match_token(vec![TokenKind::DotDot]) {
+                        let tail: Pattern = if self.check(TokenKind::Identifier) {
+                            self.pattern()?
+                        } else {
+                            Pattern::Wildcard
+                        };
+                        rest = Some(Box::new(tail));
+                        break;
+                    }
+                    elements.push(self.pattern()?);
+                    if !self.match_token(vec![TokenKind::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(
+                TokenKind::RightBracket,
+                "Expect ] after list pattern".to_string(),
+            )?;
+            return Ok(Pattern::List { elements, rest });
+        }
+        if self.match_token(vec![TokenKind::LeftBrace]) {
+            let mut entries: Vec<(Literal, Pattern)> = Vec::new();
+            if !self.check(TokenKind::RightBrace) {
+                loop {
+                    let key: Literal = self.pattern_literal()?;
+                    self.consume(TokenKind::Colon, "Expect : after map key".to_string())?;
+                    entries.push((key, self.pattern()?));
+                    if !self.match_token(vec![TokenKind::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(
+                TokenKind::RightBrace,
+                "Expect } after map pattern".to_string(),
+            )?;
+            return Ok(Pattern::Map(entries));
+        }
+        if self.match_token(vec![TokenKind::Identifier]) {
+            let name: String = self.previous().lexeme.to_string();
+            if name == "_" {
+                return Ok(Pattern::Wildcard);
+            }
+            if !self.match_token(vec![TokenKind::LeftBrace]) {
+                return Ok(Pattern::Binding(name));
+            }
+
+            //Point { x, y: 0 } binds x to the field of the same name and tests y
+            let mut fields: Vec<(String, Pattern)> = Vec::new();
+            if !self.check(TokenKind::RightBrace) {
+                loop {
+                    let field: String = self
+                        .consume(TokenKind::Identifier, "Expect field name".to_string())?
+                        .lexeme
+                        .to_string();
+                    let pattern: Pattern = if self.match_token(vec![TokenKind::Colon]) {
+                        self.pattern()?
+                    } else {
+                        Pattern::Binding(field.to_string())
+                    };
+                    fields.push((field, pattern));
+                    if !self.match_token(vec![TokenKind::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(
+                TokenKind::RightBrace,
+                "Expect } after instance pattern".to_string(),
+            )?;
+            return Ok(Pattern::Instance {
+                class: Expr::Variable {
+                    name,
+                    id: next_expr_id(),
+                    line,
+                },
+                fields,
+                line,
+            });
+        }
+
+        let start: Literal = self.pattern_literal()?;
+        if self.match_token(vec![TokenKind::DotDot, TokenKind::DotDotEqual]) {
+            let inclusive: bool = self.previous().kind == TokenKind::DotDotEqual;
+            let end: Literal = self.pattern_literal()?;
+            let is_numeric = |l: &Literal| matches!(l, Literal::Integer(_) | Literal::Number(_));
+            if !is_numeric(&start) || !is_numeric(&end) {
+                return Err(ParseError::InvalidPattern { line });
+            }
+            return Ok(Pattern::Range {
+                start,
+                end,
+                inclusive,
+            });
+        }
+        Ok(Pattern::Literal(start))
+    }
+
+    fn match_expression(&mut self) -> Result<Expr, ParseError> {
+        let line: usize = self.previous().line;
+        let subject: Expr = self.expression()?;
+        self.consume(
+            TokenKind::LeftBrace,
+            "Expect { after match value".to_string(),
+        )?;
+
+        let mut arms: Vec<MatchArm> = Vec::new();
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            let pattern: Pattern = self.pattern()?;
+            let guard: Option<Expr> = if self.match_token(vec![TokenKind::If]) {
+                Some(self.guard()?)
+            } else {
+                None
+            };
+            self.consume(
+                TokenKind::Arrow,
+                "Expect => after match pattern".to_string(),
+            )?;
+            let body: Expr = self.expression()?;
+            arms.push(MatchArm {
+                pattern,
+                guard,
+                body,
+            });
+            if !self.match_token(vec![TokenKind::Comma]) {
+                break;
+            }
+        }
+
+        self.consume(
+            TokenKind::RightBrace,
+            "Expect } after match arms".to_string(),
+        )?;
+        Ok(Expr::Match {
+            subject: Box::new(subject),
+            arms,
+            line,
+        })
+    }
+
     fn primary(&mut self) -> Result<Expr, ParseError> {
+        if self.match_token(vec![TokenKind::Match]) {
+            return self.match_expression();
+        }
+        if self.match_token(vec![TokenKind::Fun]) {
+            return self.lambda();
+        }
         if self.match_token(vec![TokenKind::LeftParen]) {
-            let expr: Expr = match self.expression() {
-                Ok(e) => e,
-                Err(err) => return Err(err),
-            };
-            match self.consume(
+            if self.allow_arrow && self.is_arrow_function() {
+                return self.arrow_function();
+            }
+            let expr: Expr = self.expression()?;
+            self.consume(
                 TokenKind::RightParen,
                 "Expect ')' after expression".to_string(),
-            ) {
-                Ok(_) => (),
-                Err(e) => return Err(e),
-            };
+            )?;
             return Ok(Expr::Grouping {
                 exp: Box::new(expr),
             });
         }
+        if self.match_token(vec![TokenKind::Super]) {
+            self.consume(TokenKind::Dot, "Expect . after super".to_string())?;
+            let method: &Token = self.consume(
+                TokenKind::Identifier,
+                "Expect superclass method name".to_string(),
+            )?;
+            return Ok(Expr::Super {
+                method: method.lexeme.to_string(),
+                id: next_expr_id(),
+                line: method.line,
+            });
+        }
+        if self.match_token(vec![TokenKind::Interpolation]) {
+            return self.interpolation();
+        }
+        if self.match_token(vec![TokenKind::LeftBracket]) {
+            let mut elements: Vec<Expr> = Vec::new();
+            if !self.check(TokenKind::RightBracket) {
+                loop {
+                    elements.push(self.expression()?);
+                    if !self.match_token(vec![TokenKind::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(
+                TokenKind::RightBracket,
+                "Expect ] after list elements".to_string(),
+            )?;
+            return Ok(Expr::List(elements));
+        }
+        //statement() claims a leading { for blocks first, so one reaching here is always a map
+        if self.match_token(vec![TokenKind::LeftBrace]) {
+            let line: usize = self.previous().line;
+            let mut entries: Vec<(Expr, Expr)> = Vec::new();
+            if !self.check(TokenKind::RightBrace) {
+                loop {
+                    let key: Expr = self.expression()?;
+                    self.consume(TokenKind::Colon, "Expect : after map key".to_string())?;
+                    let value: Expr = self.expression()?;
+                    entries.push((key, value));
+                    if !self.match_token(vec![TokenKind::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(
+                TokenKind::RightBrace,
+                "Expect } after map entries".to_string(),
+            )?;
+            return Ok(Expr::Map { entries, line });
+        }
+        if self.match_token(vec![TokenKind::This]) {
+            return Ok(Expr::This {
+                id: next_expr_id(),
+                line: self.previous().line,
+            });
+        }
         if self.match_token(vec![TokenKind::Identifier]) {
-            return Ok(Expr::Variable(self.previous().lexeme.to_string()));
+            let name: &Token = self.previous();
+            return Ok(Expr::Variable {
+                name: name.lexeme.to_string(),
+                id: next_expr_id(),
+                line: name.line,
+            });
         }
-        match parse_literal(self.peek()) {
-            Ok(l) => {
-                self.advance();
-                Ok(Expr::Literal(l))
+        let literal: Literal = parse_literal(self.peek())?;
+        self.advance();
+        Ok(Expr::Literal(literal))
+    }
+
+    //"a ${x} b ${y} c" lexes as Interpolation(a) x Interpolation(b) y StringLiteral(c)
+    fn interpolation(&mut self) -> Result<Expr, ParseError> {
+        let mut parts: Vec<Expr> = Vec::new();
+
+        loop {
+            parts.push(Expr::Literal(parse_literal(self.previous().clone())?));
+            parts.push(self.expression()?);
+
+            if !self.match_token(vec![TokenKind::Interpolation]) {
+                break;
+            }
+        }
+
+        self.consume(
+            TokenKind::StringLiteral,
+            "Expect } after interpolated expression".to_string(),
+        )?;
+        parts.push(Expr::Literal(parse_literal(self.previous().clone())?));
+
+        Ok(Expr::Interpolation(parts))
+    }
+
+    fn finish_call(&mut self, callee: Expr) -> Result<Expr, ParseError> {
+        let mut arguments: Vec<Expr> = Vec::new();
+
+        if !self.check(TokenKind::RightParen) {
+            loop {
+                arguments.push(self.expression()?);
+                if !self.match_token(vec![TokenKind::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        let line: usize = self
+            .consume(
+                TokenKind::RightParen,
+                "Expect ) after arguments".to_string(),
+            )?
+            .line;
+
+        Ok(Expr::Call {
+            callee: Box::new(callee),
+            arguments,
+            line,
+        })
+    }
+
+    fn call(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Expr = self.primary()?;
+
+        loop {
+            if self.match_token(vec![TokenKind::LeftParen]) {
+                expr = self.finish_call(expr)?;
+            } else if self.match_token(vec![TokenKind::LeftBracket]) {
+                let index: Expr = self.expression()?;
+                let line: usize = self
+                    .consume(TokenKind::RightBracket, "Expect ] after index".to_string())?
+                    .line;
+                expr = Expr::Index {
+                    object: Box::new(expr),
+                    index: Box::new(index),
+                    line,
+                };
+            } else if self.match_token(vec![TokenKind::Dot]) {
+                let name: &Token = self.consume(
+                    TokenKind::Identifier,
+                    "Expect property name after .".to_string(),
+                )?;
+                expr = Expr::Get {
+                    object: Box::new(expr),
+                    name: name.lexeme.to_string(),
+                    line: name.line,
+                };
+            } else {
+                break;
             }
-            Err(e) => Err(e),
         }
+
+        Ok(expr)
+    }
+
+    fn postfix(&mut self) -> Result<Expr, ParseError> {
+        let expr: Expr = self.call()?;
+
+        if self.match_token(vec![TokenKind::PlusPlus, TokenKind::MinusMinus]) {
+            let line: usize = self.previous().line;
+            let op: BinaryOp = parse_binary_op(self.previous())?;
+            return Ok(Expr::Increment {
+                target: Box::new(assignment_target(expr, line)?),
+                op,
+                prefix: false,
+                line,
+            });
+        }
+
+        Ok(expr)
+    }
+
+    //right associative and binds tighter than unary, so -2 ** 2 is -(2 ** 2) while the
+    //exponent itself may still be negated as in 2 ** -1
+    fn exponent(&mut self) -> Result<Expr, ParseError> {
+        let expr: Expr = self.postfix()?;
+
+        if self.match_token(vec![TokenKind::StarStar]) {
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.unary()?;
+            return Ok(Expr::Binary {
+                left: Box::new(expr),
+                op: operator,
+                right: Box::new(right),
+                line,
+            });
+        }
+
+        Ok(expr)
     }
 
     fn unary(&mut self) -> Result<Expr, ParseError> {
-        if self.match_token(vec![TokenKind::Bang, TokenKind::Minus]) {
-            let operator: UnaryOp = match parse_unary_op(self.previous()) {
-                Ok(b) => b,
-                Err(e) => return Err(e),
-            };
-            let right: Expr = match self.unary() {
-                Ok(e) => e,
-                Err(err) => return Err(err),
-            };
+        if self.match_token(vec![TokenKind::Bang, TokenKind::Minus, TokenKind::Tilde]) {
+            let line: usize = self.previous().line;
+            let operator: UnaryOp = parse_unary_op(self.previous())?;
+            let right: Expr = self.unary()?;
             return Ok(Expr::Unary {
                 op: operator,
                 right: Box::new(right),
+                line,
             });
         }
-        self.primary()
+        if self.match_token(vec![TokenKind::PlusPlus, TokenKind::MinusMinus]) {
+            let line: usize = self.previous().line;
+            let op: BinaryOp = parse_binary_op(self.previous())?;
+            let target: Expr = self.unary()?;
+            return Ok(Expr::Increment {
+                target: Box::new(assignment_target(target, line)?),
+                op,
+                prefix: true,
+                line,
+            });
+        }
+        self.exponent()
     }
 
     fn factor(&mut self) -> Result<Expr, ParseError> {
-        let mut expr: Expr = match self.unary() {
-            Ok(e) => e,
-            Err(err) => return Err(err),
-        };
+        let mut expr: Expr = self.unary()?;
 
-        while self.match_token(vec![TokenKind::Slash, TokenKind::Star]) {
-            let operator: BinaryOp = match parse_binary_op(self.previous()) {
-                Ok(b) => b,
-                Err(e) => return Err(e),
-            };
-            let right: Expr = match self.unary() {
-                Ok(e) => e,
-                Err(err) => return Err(err),
-            };
+        while self.match_token(vec![TokenKind::Slash, TokenKind::Star, TokenKind::Percent]) {
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.unary()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 op: operator,
                 right: Box::new(right),
+                line,
             }
         }
 
@@ -157,24 +598,89 @@ impl<'a> Parser<'a> {
     }
 
     fn term(&mut self) -> Result<Expr, ParseError> {
-        let mut expr: Expr = match self.factor() {
-            Ok(e) => e,
-            Err(err) => return Err(err),
-        };
+        let mut expr: Expr = self.factor()?;
 
         while self.match_token(vec![TokenKind::Minus, TokenKind::Plus]) {
-            let operator: BinaryOp = match parse_binary_op(self.previous()) {
-                Ok(b) => b,
-                Err(e) => return Err(e),
-            };
-            let right: Expr = match self.factor() {
-                Ok(e) => e,
-                Err(err) => return Err(err),
-            };
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.factor()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                op: operator,
+                right: Box::new(right),
+                line,
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn shift(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Expr = self.term()?;
+
+        while self.match_token(vec![TokenKind::LessLess, TokenKind::GreaterGreater]) {
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.term()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 op: operator,
                 right: Box::new(right),
+                line,
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Expr = self.shift()?;
+
+        while self.match_token(vec![TokenKind::Ampersand]) {
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.shift()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                op: operator,
+                right: Box::new(right),
+                line,
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_xor(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Expr = self.bitwise_and()?;
+
+        while self.match_token(vec![TokenKind::Caret]) {
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.bitwise_and()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                op: operator,
+                right: Box::new(right),
+                line,
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_or(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Expr = self.bitwise_xor()?;
+
+        while self.match_token(vec![TokenKind::Pipe]) {
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.bitwise_xor()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                op: operator,
+                right: Box::new(right),
+                line,
             }
         }
 
@@ -182,10 +688,7 @@ impl<'a> Parser<'a> {
     }
 
     fn comparison(&mut self) -> Result<Expr, ParseError> {
-        let mut expr: Expr = match self.term() {
-            Ok(e) => e,
-            Err(err) => return Err(err),
-        };
+        let mut expr: Expr = self.bitwise_or()?;
 
         while self.match_token(vec![
             TokenKind::GreaterEqual,
@@ -193,18 +696,14 @@ impl<'a> Parser<'a> {
             TokenKind::LessEqual,
             TokenKind::LessThan,
         ]) {
-            let operator: BinaryOp = match parse_binary_op(self.previous()) {
-                Ok(b) => b,
-                Err(e) => return Err(e),
-            };
-            let right: Expr = match self.term() {
-                Ok(e) => e,
-                Err(err) => return Err(err),
-            };
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.bitwise_or()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 op: operator,
                 right: Box::new(right),
+                line,
             }
         }
 
@@ -212,111 +711,661 @@ impl<'a> Parser<'a> {
     }
 
     fn equality(&mut self) -> Result<Expr, ParseError> {
-        let mut expr: Expr = match self.comparison() {
-            Ok(e) => e,
-            Err(err) => return Err(err),
-        };
+        let mut expr: Expr = self.comparison()?;
 
         while self.match_token(vec![TokenKind::EqualEqual, TokenKind::BangEqual]) {
-            let operator: BinaryOp = match parse_binary_op(self.previous()) {
-                Ok(b) => b,
-                Err(e) => return Err(e),
-            };
-            let right: Expr = match self.comparison() {
-                Ok(e) => e,
-                Err(err) => return Err(err),
-            };
+            let line: usize = self.previous().line;
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.comparison()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 op: operator,
                 right: Box::new(right),
+                line,
             }
         }
 
         Ok(expr)
     }
 
-    fn expression(&mut self) -> Result<Expr, ParseError> {
-        match self.equality() {
-            Ok(e) => Ok(e),
-            Err(err) => return Err(err),
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Expr = self.equality()?;
+
+        while self.match_token(vec![TokenKind::And]) {
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.equality()?;
+            expr = Expr::Logical {
+                left: Box::new(expr),
+                op: operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Expr = self.and()?;
+
+        while self.match_token(vec![TokenKind::Or]) {
+            let operator: BinaryOp = parse_binary_op(self.previous())?;
+            let right: Expr = self.and()?;
+            expr = Expr::Logical {
+                left: Box::new(expr),
+                op: operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expr)
+    }
+
+    //right associative, so a ? b : c ? d : e groups as a ? b : (c ? d : e)
+    fn conditional(&mut self) -> Result<Expr, ParseError> {
+        let expr: Expr = self.or()?;
+
+        if self.match_token(vec![TokenKind::Question]) {
+            let then_branch: Expr = self.expression()?;
+            self.consume(
+                TokenKind::Colon,
+                "Expect : after then branch of conditional".to_string(),
+            )?;
+            let else_branch: Expr = self.conditional()?;
+            return Ok(Expr::Conditional {
+                condition: Box::new(expr),
+                then_branch: Box::new(then_branch),
+                else_branch: Box::new(else_branch),
+            });
+        }
+
+        Ok(expr)
+    }
+
+    //right associative, so parse the value side recursively before building the node
+    fn assignment(&mut self) -> Result<Expr, ParseError> {
+        let expr: Expr = self.conditional()?;
+
+        if self.match_token(vec![TokenKind::Equal]) {
+            let line: usize = self.previous().line;
+            let value: Expr = self.assignment()?;
+
+            return match expr {
+                Expr::Variable { name, .. } => Ok(Expr::Assign {
+                    name,
+                    value: Box::new(value),
+                    id: next_expr_id(),
+                    line,
+                }),
+                Expr::Get { object, name, line } => Ok(Expr::Set {
+                    object,
+                    name,
+                    value: Box::new(value),
+                    line,
+                }),
+                Expr::Index {
+                    object,
+                    index,
+                    line,
+                } => Ok(Expr::IndexSet {
+                    object,
+                    index,
+                    value: Box::new(value),
+                    line,
+                }),
+                _ => Err(ParseError::InvalidAssignmentTarget { line }),
+            };
+        }
+
+        if self.match_token(vec![
+            TokenKind::PlusEqual,
+            TokenKind::MinusEqual,
+            TokenKind::StarEqual,
+            TokenKind::SlashEqual,
+        ]) {
+            let line: usize = self.previous().line;
+            let op: BinaryOp = parse_binary_op(self.previous())?;
+            let value: Expr = self.assignment()?;
+
+            return Ok(Expr::CompoundAssign {
+                target: Box::new(assignment_target(expr, line)?),
+                op,
+                value: Box::new(value),
+                line,
+            });
         }
+
+        Ok(expr)
+    }
+
+    //anything nested in brackets comes back through here, so arrows are allowed again inside them
+    fn expression(&mut self) -> Result<Expr, ParseError> {
+        let allow_arrow: bool = std::mem::replace(&mut self.allow_arrow, true);
+        let expr = self.assignment();
+        self.allow_arrow = allow_arrow;
+        expr
+    }
+
+    fn guard(&mut self) -> Result<Expr, ParseError> {
+        let allow_arrow: bool = std::mem::replace(&mut self.allow_arrow, false);
+        let expr = self.assignment();
+        self.allow_arrow = allow_arrow;
+        expr
     }
 
     fn expression_statement(&mut self) -> Result<Stmt, ParseError> {
-        let expr: Expr = match self.expression() {
-            Ok(e) => e,
-            Err(err) => return Err(err),
-        };
-        self.consume(TokenKind::Semicolon, "Expect ; after statement".to_string());
+        let expr: Expr = self.expression()?;
+        self.consume(TokenKind::Semicolon, "Expect ; after statement".to_string())?;
         Ok(Stmt::Expression(expr))
     }
 
     fn print_statement(&mut self) -> Result<Stmt, ParseError> {
-        let expr: Expr = match self.expression() {
-            Ok(e) => e,
-            Err(err) => return Err(err),
-        };
-        self.consume(TokenKind::Semicolon, "Expect ; after statement".to_string());
+        let expr: Expr = self.expression()?;
+        self.consume(TokenKind::Semicolon, "Expect ; after statement".to_string())?;
         Ok(Stmt::Print(expr))
     }
 
+    fn block(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        let mut statements: Vec<Stmt> = Vec::new();
+
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            statements.push(self.declaration()?);
+        }
+
+        self.consume(TokenKind::RightBrace, "Expect } after block".to_string())?;
+        Ok(statements)
+    }
+
+    //an else always binds to the nearest if since the inner if_statement consumes it first
+    fn if_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenKind::LeftParen, "Expect ( after if".to_string())?;
+        let condition: Expr = self.expression()?;
+        self.consume(
+            TokenKind::RightParen,
+            "Expect ) after if condition".to_string(),
+        )?;
+
+        let then_branch: Stmt = self.statement()?;
+        let mut else_branch: Option<Box<Stmt>> = None;
+        if self.match_token(vec![TokenKind::Else]) {
+            else_branch = Some(Box::new(self.statement()?));
+        }
+
+        Ok(Stmt::If {
+            condition,
+            then_branch: Box::new(then_branch),
+            else_branch,
+        })
+    }
+
+    fn loop_body(&mut self, label: &Option<String>) -> Result<Stmt, ParseError> {
+        self.loop_labels.push(label.clone());
+        let body = self.statement();
+        self.loop_labels.pop();
+        body
+    }
+
+    fn while_statement(&mut self, label: Option<String>) -> Result<Stmt, ParseError> {
+        self.consume(TokenKind::LeftParen, "Expect ( after while".to_string())?;
+        let condition: Expr = self.expression()?;
+        self.consume(
+            TokenKind::RightParen,
+            "Expect ) after while condition".to_string(),
+        )?;
+        let body: Stmt = self.loop_body(&label)?;
+
+        Ok(Stmt::While {
+            condition,
+            body: Box::new(body),
+            increment: None,
+            label,
+        })
+    }
+
+    //desugars for (init; cond; incr) body into { init; while (cond) body } with incr run after
+    //each pass of the body so continue still reaches it
+    fn for_statement(&mut self, label: Option<String>) -> Result<Stmt, ParseError> {
+        self.consume(TokenKind::LeftParen, "Expect ( after for".to_string())?;
+
+        let initializer: Option<Stmt> = if self.match_token(vec![TokenKind::Semicolon]) {
+            None
+        } else if self.match_token(vec![TokenKind::Var]) {
+            Some(self.var_declaration(true)?)
+        } else {
+            Some(self.expression_statement()?)
+        };
+
+        let mut condition: Option<Expr> = None;
+        if !self.check(TokenKind::Semicolon) {
+            condition = Some(self.expression()?);
+        }
+        self.consume(
+            TokenKind::Semicolon,
+            "Expect ; after loop condition".to_string(),
+        )?;
+
+        let mut increment: Option<Expr> = None;
+        if !self.check(TokenKind::RightParen) {
+            increment = Some(self.expression()?);
+        }
+        self.consume(
+            TokenKind::RightParen,
+            "Expect ) after for clauses".to_string(),
+        )?;
+
+        let mut body: Stmt = self.loop_body(&label)?;
+
+        body = Stmt::While {
+            condition: condition.unwrap_or(Expr::Literal(Literal::True)),
+            body: Box::new(body),
+            increment,
+            label,
+        };
+
+        if let Some(initializer) = initializer {
+            body = Stmt::Block(vec![initializer, body]);
+        }
+
+        Ok(body)
+    }
+
+    fn return_statement(&mut self) -> Result<Stmt, ParseError> {
+        let line: usize = self.previous().line;
+        let mut value: Option<Expr> = None;
+        if !self.check(TokenKind::Semicolon) {
+            value = Some(self.expression()?);
+        }
+
+        self.consume(
+            TokenKind::Semicolon,
+            "Expect ; after return value".to_string(),
+        )?;
+        Ok(Stmt::Return { value, line })
+    }
+
+    //as and from are only special inside an import, so they stay usable as identifiers
+    fn consume_word(&mut self, word: &str, message: String) -> Result<(), ParseError> {
+        if self.check(TokenKind::Identifier) && self.peek().lexeme == word {
+            self.advance();
+            return Ok(());
+        }
+        Err(ParseError::InvalidGrouping(message))
+    }
+
+    fn import_statement(&mut self) -> Result<Stmt, ParseError> {
+        let line: usize = self.previous().line;
+
+        let mut names: Vec<String> = Vec::new();
+        if self.match_token(vec![TokenKind::LeftBrace]) {
+            loop {
+                let name: String = self
+                    .consume(TokenKind::Identifier, "Expect imported name".to_string())?
+                    .lexeme
+                    .to_string();
+                names.push(name);
+                if !self.match_token(vec![TokenKind::Comma]) {
+                    break;
+                }
+            }
+            self.consume(
+                TokenKind::RightBrace,
+                "Expect } after imported names".to_string(),
+            )?;
+            self.consume_word("from", "Expect from after imported names".to_string())?;
+        }
+
+        let path: String = match self
+            .consume(TokenKind::StringLiteral, "Expect module path".to_string())?
+            .literal
+            .clone()
+        {
+            Some(Literal::StringLiteral(path)) => path,
+            _ => {
+                return Err(ParseError::InvalidGrouping(
+                    "Expect module path".to_string(),
+                ));
+            }
+        };
+
+        let mut alias: Option<String> = None;
+        if names.is_empty() {
+            self.consume_word("as", "Expect as after module path".to_string())?;
+            alias = Some(
+                self.consume(TokenKind::Identifier, "Expect module name".to_string())?
+                    .lexeme
+                    .to_string(),
+            );
+        }
+
+        self.consume(TokenKind::Semicolon, "Expect ; after import".to_string())?;
+        Ok(Stmt::Import {
+            path,
+            alias,
+            names,
+            line,
+        })
+    }
+
+    fn throw_statement(&mut self) -> Result<Stmt, ParseError> {
+        let line: usize = self.previous().line;
+        let value: Expr = self.expression()?;
+        self.consume(
+            TokenKind::Semicolon,
+            "Expect ; after thrown value".to_string(),
+        )?;
+        Ok(Stmt::Throw { value, line })
+    }
+
+    fn try_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenKind::LeftBrace, "Expect { after try".to_string())?;
+        let body: Vec<Stmt> = self.block()?;
+
+        let mut catch: Option<(String, Vec<Stmt>)> = None;
+        if self.match_token(vec![TokenKind::Catch]) {
+            self.consume(TokenKind::LeftParen, "Expect ( after catch".to_string())?;
+            let name: String = self
+                .consume(TokenKind::Identifier, "Expect error name".to_string())?
+                .lexeme
+                .to_string();
+            self.consume(
+                TokenKind::RightParen,
+                "Expect ) after error name".to_string(),
+            )?;
+            self.consume(
+                TokenKind::LeftBrace,
+                "Expect { after catch clause".to_string(),
+            )?;
+            catch = Some((name, self.block()?));
+        }
+
+        let mut finally: Option<Vec<Stmt>> = None;
+        if self.match_token(vec![TokenKind::Finally]) {
+            self.consume(TokenKind::LeftBrace, "Expect { after finally".to_string())?;
+            finally = Some(self.block()?);
+        }
+
+        if catch.is_none() && finally.is_none() {
+            return Err(ParseError::InvalidGrouping(
+                "Expect catch or finally after try block".to_string(),
+            ));
+        }
+        Ok(Stmt::Try {
+            body,
+            catch,
+            finally,
+        })
+    }
+
+    //break and continue share everything except the statement they build
+    fn loop_control_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword: Token = self.previous().clone();
+
+        let mut label: Option<String> = None;
+        if self.match_token(vec![TokenKind::Identifier]) {
+            label = Some(self.previous().lexeme.to_string());
+        }
+        self.consume(
+            TokenKind::Semicolon,
+            format!("Expect ; after {}", keyword.lexeme),
+        )?;
+
+        if self.loop_labels.is_empty() {
+            return Err(ParseError::InvalidLoopControl {
+                keyword: keyword.lexeme,
+                line: keyword.line,
+            });
+        }
+        if let Some(name) = &label
+            && !self.loop_labels.contains(&label)
+        {
+            return Err(ParseError::UndefinedLabel {
+                label: name.to_string(),
+                line: keyword.line,
+            });
+        }
+
+        match keyword.kind {
+            TokenKind::Break => Ok(Stmt::Break { label }),
+            _ => Ok(Stmt::Continue { label }),
+        }
+    }
+
+    fn labeled_statement(&mut self) -> Result<Stmt, ParseError> {
+        let label: String = self.advance().lexeme.to_string();
+        self.advance();
+
+        if self.match_token(vec![TokenKind::For]) {
+            return self.for_statement(Some(label));
+        }
+        if self.match_token(vec![TokenKind::While]) {
+            return self.while_statement(Some(label));
+        }
+        Err(ParseError::InvalidGrouping(format!(
+            "Expect loop after label {}",
+            label
+        )))
+    }
+
     fn statement(&mut self) -> Result<Stmt, ParseError> {
+        if self.check(TokenKind::Identifier) && self.peek_next().kind == TokenKind::Colon {
+            return self.labeled_statement();
+        }
+        if self.match_token(vec![TokenKind::Break, TokenKind::Continue]) {
+            return self.loop_control_statement();
+        }
+        if self.match_token(vec![TokenKind::For]) {
+            return self.for_statement(None);
+        }
+        if self.match_token(vec![TokenKind::If]) {
+            return self.if_statement();
+        }
+        if self.match_token(vec![TokenKind::While]) {
+            return self.while_statement(None);
+        }
         if self.match_token(vec![TokenKind::Print]) {
-            return match self.print_statement() {
-                Ok(s) => Ok(s),
-                Err(e) => Err(e),
-            };
+            return self.print_statement();
+        }
+        if self.match_token(vec![TokenKind::Return]) {
+            return self.return_statement();
+        }
+        if self.match_token(vec![TokenKind::Import]) {
+            return self.import_statement();
+        }
+        if self.match_token(vec![TokenKind::Throw]) {
+            return self.throw_statement();
+        }
+        if self.match_token(vec![TokenKind::Try]) {
+            return self.try_statement();
+        }
+        if self.match_token(vec![TokenKind::LeftBrace]) {
+            return Ok(Stmt::Block(self.block()?));
         }
         self.expression_statement()
     }
 
-    fn var_declaration(&mut self) -> Result<Stmt, ParseError> {
-        let name: String = match self.consume(
+    fn type_annotation(&mut self) -> Result<TypeAnnotation, ParseError> {
+        if self.match_token(vec![TokenKind::Null]) {
+            return Ok(TypeAnnotation::Null);
+        }
+        if self.match_token(vec![TokenKind::Fun]) {
+            return Ok(TypeAnnotation::Function);
+        }
+        let name: String = self
+            .consume(TokenKind::Identifier, "Expect type name".to_string())?
+            .lexeme
+            .to_string();
+        Ok(match name.as_str() {
+            "int" => TypeAnnotation::Integer,
+            "float" => TypeAnnotation::Float,
+            "number" => TypeAnnotation::Number,
+            "string" => TypeAnnotation::String,
+            "bool" => TypeAnnotation::Boolean,
+            "list" => TypeAnnotation::List,
+            "map" => TypeAnnotation::Map,
+            "function" => TypeAnnotation::Function,
+            "any" => TypeAnnotation::Any,
+            _ => TypeAnnotation::Class(name),
+        })
+    }
+
+    fn optional_annotation(&mut self) -> Result<Option<TypeAnnotation>, ParseError> {
+        if self.match_token(vec![TokenKind::Colon]) {
+            return Ok(Some(self.type_annotation()?));
+        }
+        Ok(None)
+    }
+
+    fn var_declaration(&mut self, mutable: bool) -> Result<Stmt, ParseError> {
+        let keyword: String = self.previous().lexeme.to_string();
+        let name_token: &Token = self.consume(
             TokenKind::Identifier,
-            "Expect identifier after var declaration".to_string(),
-        ) {
-            Ok(t) => t.lexeme.to_string(),
-            Err(e) => return Err(e),
-        };
+            format!("Expect identifier after {} declaration", keyword),
+        )?;
+        let name: String = name_token.lexeme.to_string();
+        let line: usize = name_token.line;
+        let annotation: Option<TypeAnnotation> = self.optional_annotation()?;
 
         let mut initializer: Option<Expr> = None;
         if self.match_token(vec![TokenKind::Equal]) {
-            initializer = match self.expression() {
-                Ok(e) => Some(e),
-                Err(err) => return Err(err),
-            };
+            initializer = Some(self.expression()?);
+        }
+        if !mutable && initializer.is_none() {
+            return Err(ParseError::UninitializedConstant { name, line });
         }
 
         self.consume(
             TokenKind::Semicolon,
             "Expect ; after declaration".to_string(),
-        );
+        )?;
         Ok(Stmt::Var {
-            name: name,
-            initializer: initializer,
+            name,
+            initializer,
+            mutable,
+            annotation,
+            line,
         })
     }
 
-    pub fn declaration(&mut self) -> Result<Stmt, ParseError> {
-        //huge note here that this stops errors from being thrown and just evaluates what it can.
-        //flaw but useful right now for checking how the program works.
-        if self.match_token(vec![TokenKind::Var]) {
-            match self.var_declaration() {
-                Ok(stmt) => return Ok(stmt),
-                Err(e) => {
-                    self.synchronize();
-                    return Err(e);
+    fn function(&mut self, kind: &str) -> Result<FunctionDecl, ParseError> {
+        let name_token: &Token =
+            self.consume(TokenKind::Identifier, format!("Expect {} name", kind))?;
+        let name: String = name_token.lexeme.to_string();
+        let line: usize = name_token.line;
+
+        self.consume(
+            TokenKind::LeftParen,
+            format!("Expect ( after {} name", kind),
+        )?;
+        let (params, param_types) = self.parameters()?;
+        let return_type: Option<TypeAnnotation> = self.optional_annotation()?;
+        let body: Vec<Stmt> = self.function_body(kind)?;
+
+        Ok(FunctionDecl {
+            name,
+            params,
+            param_types,
+            return_type,
+            body,
+            line,
+        })
+    }
+
+    //called with the opening ( already consumed
+    fn parameters(&mut self) -> Result<(Vec<String>, Vec<Option<TypeAnnotation>>), ParseError> {
+        let mut params: Vec<String> = Vec::new();
+        let mut param_types: Vec<Option<TypeAnnotation>> = Vec::new();
+        if !self.check(TokenKind::RightParen) {
+            loop {
+                let param: String = self
+                    .consume(TokenKind::Identifier, "Expect parameter name".to_string())?
+                    .lexeme
+                    .to_string();
+                params.push(param);
+                param_types.push(self.optional_annotation()?);
+                if !self.match_token(vec![TokenKind::Comma]) {
+                    break;
                 }
             }
         }
-        match self.statement() {
-            Ok(stmt) => return Ok(stmt),
-            Err(e) => {
-                self.synchronize();
-                return Err(e);
-            }
+        self.consume(
+            TokenKind::RightParen,
+            "Expect ) after parameters".to_string(),
+        )?;
+        Ok((params, param_types))
+    }
+
+    fn function_body(&mut self, kind: &str) -> Result<Vec<Stmt>, ParseError> {
+        self.consume(
+            TokenKind::LeftBrace,
+            format!("Expect {{ before {} body", kind),
+        )?;
+        //loops outside the function can't be broken out of from inside it
+        let enclosing_loops: Vec<Option<String>> = std::mem::take(&mut self.loop_labels);
+        let body = self.block();
+        self.loop_labels = enclosing_loops;
+        body
+    }
+
+    fn class_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name_token: &Token =
+            self.consume(TokenKind::Identifier, "Expect class name".to_string())?;
+        let name: String = name_token.lexeme.to_string();
+        let line: usize = name_token.line;
+
+        let mut superclass: Option<Expr> = None;
+        if self.match_token(vec![TokenKind::LessThan]) {
+            let superclass_name: &Token =
+                self.consume(TokenKind::Identifier, "Expect superclass name".to_string())?;
+            superclass = Some(Expr::Variable {
+                name: superclass_name.lexeme.to_string(),
+                id: next_expr_id(),
+                line: superclass_name.line,
+            });
+        }
+
+        self.consume(
+            TokenKind::LeftBrace,
+            "Expect { before class body".to_string(),
+        )?;
+
+        let mut methods: Vec<Rc<FunctionDecl>> = Vec::new();
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            methods.push(Rc::new(self.function("method")?));
         }
+
+        self.consume(
+            TokenKind::RightBrace,
+            "Expect } after class body".to_string(),
+        )?;
+        Ok(Stmt::Class {
+            name,
+            superclass,
+            methods,
+            line,
+        })
+    }
+
+    pub fn declaration(&mut self) -> Result<Stmt, ParseError> {
+        //huge note here that this stops errors from being thrown and just evaluates what it can.
+        //flaw but useful right now for checking how the program works.
+        let result = if self.match_token(vec![TokenKind::Class]) {
+            self.class_declaration()
+        } else if self.check(TokenKind::Fun) && self.peek_next().kind == TokenKind::Identifier {
+            //a fun not followed by a name is a lambda expression, left for statement()
+            self.advance();
+            self.function("function")
+                .map(|declaration| Stmt::Function(Rc::new(declaration)))
+        } else if self.match_token(vec![TokenKind::Var]) {
+            self.var_declaration(true)
+        } else if self.match_token(vec![TokenKind::Const, TokenKind::Let]) {
+            self.var_declaration(false)
+        } else {
+            self.statement()
+        };
+
+        if result.is_err() {
+            self.synchronize();
+        }
+        result
     }
 }
 
@@ -336,7 +1385,11 @@ pub fn parse_tokens(tokens: &Vec<Token>) -> Result<Vec<Stmt>, ParseError> {
         };
     }
 
-    Ok(statements)
+    //keep parsing past errors so synchronize is exercised, but only report the first one
+    match errors.into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(statements),
+    }
 }
 
 pub fn parse_binary_op(token: &Token) -> Result<BinaryOp, ParseError> {
@@ -347,6 +1400,17 @@ pub fn parse_binary_op(token: &Token) -> Result<BinaryOp, ParseError> {
         TokenKind::Minus => Ok(BinaryOp::Minus),
         TokenKind::Star => Ok(BinaryOp::Star),
         TokenKind::Slash => Ok(BinaryOp::Slash),
+        TokenKind::Percent => Ok(BinaryOp::Percent),
+        TokenKind::PlusEqual | TokenKind::PlusPlus => Ok(BinaryOp::Plus),
+        TokenKind::MinusEqual | TokenKind::MinusMinus => Ok(BinaryOp::Minus),
+        TokenKind::StarEqual => Ok(BinaryOp::Star),
+        TokenKind::SlashEqual => Ok(BinaryOp::Slash),
+        TokenKind::StarStar => Ok(BinaryOp::StarStar),
+        TokenKind::Ampersand => Ok(BinaryOp::Ampersand),
+        TokenKind::Pipe => Ok(BinaryOp::Pipe),
+        TokenKind::Caret => Ok(BinaryOp::Caret),
+        TokenKind::LessLess => Ok(BinaryOp::LessLess),
+        TokenKind::GreaterGreater => Ok(BinaryOp::GreaterGreater),
         TokenKind::GreaterEqual => Ok(BinaryOp::GreaterEqual),
         TokenKind::GreaterThan => Ok(BinaryOp::GreaterThan),
         TokenKind::EqualEqual => Ok(BinaryOp::EqualEqual),
@@ -360,9 +1424,18 @@ pub fn parse_binary_op(token: &Token) -> Result<BinaryOp, ParseError> {
     }
 }
 
-pub fn parse_unary_op(token: &Token) -> Result<UnaryOp, ParseError> {
+pub fn assignment_target(expr: Expr, line: usize) -> Result<Expr, ParseError> {
+    match expr {
+        Expr::Variable { .. } | Expr::Get { .. } | Expr::Index { .. } => Ok(expr),
+        _ => Err(ParseError::InvalidAssignmentTarget { line }),
+    }
+}
+
+fn parse_unary_op(token: &Token) -> Result<UnaryOp, ParseError> {
     match token.kind {
         TokenKind::Bang => Ok(UnaryOp::Bang),
+        TokenKind::Minus => Ok(UnaryOp::Minus),
+        TokenKind::Tilde => Ok(UnaryOp::Tilde),
         _ => Err(ParseError::InvalidConversion(
             "could not convert to unary operator".to_string(),
         )),
@@ -378,7 +1451,7 @@ pub fn parse_literal(token: Token) -> Result<Literal, ParseError> {
                 line: token.line,
             }),
         },
-        TokenKind::StringLiteral => match token.literal {
+        TokenKind::StringLiteral | TokenKind::Interpolation => match token.literal {
             Some(l) => Ok(l.clone()),
             None => Err(ParseError::MissingValue {
                 val: token.lexeme.clone(),
@@ -0,0 +1,584 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::{
+    errors::resolve_error::ResolveError,
+    lexing::lexer::lex_program,
+    parsing::ast::{Expr, FunctionDecl, MatchArm, Pattern, Stmt},
+    parsing::parser::parse_tokens,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionType {
+    None,
+    Function,
+    Method,
+    Initializer,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ClassType {
+    None,
+    Class,
+    Subclass,
+}
+
+struct Resolver {
+    //each scope maps a name to whether its initializer has finished resolving
+    scopes: Vec<HashMap<String, bool>>,
+    //const and let names, one set per scope in scopes plus one for the globals
+    constants: Vec<HashSet<String>>,
+    global_constants: HashSet<String>,
+    locals: HashMap<usize, usize>,
+    current_function: FunctionType,
+    current_class: ClassType,
+    //imports are found relative to the directory of the file being resolved
+    directory: PathBuf,
+}
+
+impl Resolver {
+    pub fn new(directory: PathBuf) -> Self {
+        Resolver {
+            scopes: Vec::new(),
+            constants: Vec::new(),
+            global_constants: HashSet::new(),
+            locals: HashMap::new(),
+            current_function: FunctionType::None,
+            current_class: ClassType::None,
+            directory,
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+        self.constants.push(HashSet::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+        self.constants.pop();
+    }
+
+    fn define_constant(&mut self, name: &str) {
+        self.define(name);
+        match self.constants.last_mut() {
+            Some(constants) => constants.insert(name.to_string()),
+            None => self.global_constants.insert(name.to_string()),
+        };
+    }
+
+    //finds the scope the name resolves to the same way resolve_local does. a global constant
+    //declared later in the file is left for the runtime check
+    fn check_assignable(&self, name: &str, line: usize) -> Result<(), ResolveError> {
+        let error = || ResolveError::AssignToConstant {
+            name: name.to_string(),
+            line,
+        };
+        for (scope, constants) in self.scopes.iter().zip(self.constants.iter()).rev() {
+            if scope.contains_key(name) {
+                return match constants.contains(name) {
+                    true => Err(error()),
+                    false => Ok(()),
+                };
+            }
+        }
+        match self.global_constants.contains(name) {
+            true => Err(error()),
+            false => Ok(()),
+        }
+    }
+
+    //compound assignment and ++/-- write to their target just like plain assignment
+    fn check_target_assignable(&self, target: &Expr, line: usize) -> Result<(), ResolveError> {
+        match target {
+            Expr::Variable { name, .. } => self.check_assignable(name, line),
+            _ => Ok(()),
+        }
+    }
+
+    fn declare(&mut self, name: &str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), false);
+        }
+    }
+
+    fn define(&mut self, name: &str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), true);
+        }
+    }
+
+    //names not found in any scope are left out of locals and looked up as globals
+    fn resolve_local(&mut self, name: &str, id: usize) {
+        for (distance, scope) in self.scopes.iter().rev().enumerate() {
+            if scope.contains_key(name) {
+                self.locals.insert(id, distance);
+                return;
+            }
+        }
+    }
+
+    fn resolve_function(
+        &mut self,
+        declaration: &FunctionDecl,
+        function_type: FunctionType,
+    ) -> Result<(), ResolveError> {
+        let enclosing_function = self.current_function;
+        self.current_function = function_type;
+
+        self.begin_scope();
+        for param in declaration.params.iter() {
+            self.declare(param);
+            self.define(param);
+        }
+        let result = self.resolve_statements(&declaration.body);
+        self.end_scope();
+
+        self.current_function = enclosing_function;
+        result
+    }
+
+    fn resolve_class(
+        &mut self,
+        name: &str,
+        superclass: &Option<Expr>,
+        methods: &Vec<Rc<FunctionDecl>>,
+        line: usize,
+    ) -> Result<(), ResolveError> {
+        self.declare(name);
+        self.define(name);
+
+        if let Some(superclass) = superclass {
+            if let Expr::Variable {
+                name: superclass_name,
+                ..
+            } = superclass
+                && superclass_name == name
+            {
+                return Err(ResolveError::SelfInheritance {
+                    name: name.to_string(),
+                    line,
+                });
+            }
+            self.current_class = ClassType::Subclass;
+            self.resolve_expr(superclass)?;
+
+            self.begin_scope();
+            self.define("super");
+        }
+
+        self.begin_scope();
+        self.define("this");
+
+        let mut result = Ok(());
+        for method in methods {
+            let function_type = if method.name == "init" {
+                FunctionType::Initializer
+            } else {
+                FunctionType::Method
+            };
+            result = self.resolve_function(method, function_type);
+            if result.is_err() {
+                break;
+            }
+        }
+
+        self.end_scope();
+        if superclass.is_some() {
+            self.end_scope();
+        }
+        result
+    }
+
+    //class names are resolved in the enclosing scope, bound names are collected for the arm scope
+    fn resolve_pattern(
+        &mut self,
+        pattern: &Pattern,
+        bindings: &mut Vec<String>,
+    ) -> Result<(), ResolveError> {
+        match pattern {
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Range { .. } => Ok(()),
+            Pattern::Binding(name) => {
+                bindings.push(name.to_string());
+                Ok(())
+            }
+            Pattern::List { elements, rest } => {
+                for element in elements {
+                    self.resolve_pattern(element, bindings)?;
+                }
+                match rest {
+                    Some(rest) => self.resolve_pattern(rest, bindings),
+                    None => Ok(()),
+                }
+            }
+            Pattern::Map(entries) => {
+                for (_, value) in entries {
+                    self.resolve_pattern(value, bindings)?;
+                }
+                Ok(())
+            }
+            Pattern::Instance { class, fields, .. } => {
+                self.resolve_expr(class)?;
+                for (_, field) in fields {
+                    self.resolve_pattern(field, bindings)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn resolve_match_arm(&mut self, arm: &MatchArm) -> Result<(), ResolveError> {
+        let mut bindings: Vec<String> = Vec::new();
+        self.resolve_pattern(&arm.pattern, &mut bindings)?;
+
+        self.begin_scope();
+        for binding in bindings.iter() {
+            self.declare(binding);
+            self.define(binding);
+        }
+        let mut result = Ok(());
+        if let Some(guard) = &arm.guard {
+            result = self.resolve_expr(guard);
+        }
+        if result.is_ok() {
+            result = self.resolve_expr(&arm.body);
+        }
+        self.end_scope();
+        result
+    }
+
+    fn resolve_statements(&mut self, statements: &Vec<Stmt>) -> Result<(), ResolveError> {
+        for statement in statements {
+            self.resolve_stmt(statement)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_stmt_block(&mut self, statements: &Vec<Stmt>) -> Result<(), ResolveError> {
+        self.begin_scope();
+        let result = self.resolve_statements(statements);
+        self.end_scope();
+        result
+    }
+
+    fn resolve_stmt(&mut self, stmt: &Stmt) -> Result<(), ResolveError> {
+        match stmt {
+            Stmt::Print(e) => self.resolve_expr(e),
+            Stmt::Expression(e) => self.resolve_expr(e),
+            Stmt::Var {
+                name,
+                initializer,
+                mutable,
+                ..
+            } => {
+                self.declare(name);
+                if let Some(initializer) = initializer {
+                    self.resolve_expr(initializer)?;
+                }
+                if *mutable {
+                    self.define(name);
+                } else {
+                    self.define_constant(name);
+                }
+                Ok(())
+            }
+            Stmt::Block(statements) => self.resolve_stmt_block(statements),
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.resolve_expr(condition)?;
+                self.resolve_stmt(then_branch)?;
+                match else_branch {
+                    Some(else_branch) => self.resolve_stmt(else_branch),
+                    None => Ok(()),
+                }
+            }
+            Stmt::While {
+                condition,
+                body,
+                increment,
+                ..
+            } => {
+                self.resolve_expr(condition)?;
+                self.resolve_stmt(body)?;
+                match increment {
+                    Some(increment) => self.resolve_expr(increment),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Break { .. } | Stmt::Continue { .. } => Ok(()),
+            Stmt::Function(declaration) => {
+                //defined before the body so the function can refer to itself recursively
+                self.declare(&declaration.name);
+                self.define(&declaration.name);
+                self.resolve_function(declaration, FunctionType::Function)
+            }
+            Stmt::Return { value, line } => {
+                if self.current_function == FunctionType::None {
+                    return Err(ResolveError::TopLevelReturn { line: *line });
+                }
+                match value {
+                    Some(value) => {
+                        if self.current_function == FunctionType::Initializer {
+                            return Err(ResolveError::ReturnFromInitializer { line: *line });
+                        }
+                        self.resolve_expr(value)
+                    }
+                    None => Ok(()),
+                }
+            }
+            Stmt::Throw { value, .. } => self.resolve_expr(value),
+            Stmt::Import {
+                path, alias, names, ..
+            } => {
+                if let Some(alias) = alias {
+                    self.declare(alias);
+                    self.define(alias);
+                }
+                //a name the module declared with const or let stays constant once imported
+                let constants: HashSet<String> = match names.is_empty() {
+                    true => HashSet::new(),
+                    false => module_constants(&self.directory.join(path), &mut HashSet::new()),
+                };
+                for name in names {
+                    self.declare(name);
+                    match constants.contains(name) {
+                        true => self.define_constant(name),
+                        false => self.define(name),
+                    }
+                }
+                Ok(())
+            }
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                self.resolve_stmt_block(body)?;
+                if let Some((name, catch_body)) = catch {
+                    self.begin_scope();
+                    self.declare(name);
+                    self.define(name);
+                    let result = self.resolve_statements(catch_body);
+                    self.end_scope();
+                    result?;
+                }
+                match finally {
+                    Some(finally) => self.resolve_stmt_block(finally),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Class {
+                name,
+                superclass,
+                methods,
+                line,
+            } => {
+                let enclosing_class = self.current_class;
+                self.current_class = ClassType::Class;
+                let result = self.resolve_class(name, superclass, methods, *line);
+                self.current_class = enclosing_class;
+                result
+            }
+        }
+    }
+
+    fn resolve_expr(&mut self, expr: &Expr) -> Result<(), ResolveError> {
+        match expr {
+            Expr::Binary { left, right, .. } => {
+                self.resolve_expr(left)?;
+                self.resolve_expr(right)
+            }
+            Expr::Logical { left, right, .. } => {
+                self.resolve_expr(left)?;
+                self.resolve_expr(right)
+            }
+            Expr::Match { subject, arms, .. } => {
+                self.resolve_expr(subject)?;
+                for arm in arms {
+                    self.resolve_match_arm(arm)?;
+                }
+                Ok(())
+            }
+            Expr::Lambda(declaration) => self.resolve_function(declaration, FunctionType::Function),
+            Expr::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.resolve_expr(condition)?;
+                self.resolve_expr(then_branch)?;
+                self.resolve_expr(else_branch)
+            }
+            Expr::Unary { right, .. } => self.resolve_expr(right),
+            Expr::Grouping { exp } => self.resolve_expr(exp),
+            Expr::Literal(_) => Ok(()),
+            Expr::Variable { name, id, line } => {
+                if let Some(scope) = self.scopes.last()
+                    && scope.get(name) == Some(&false)
+                {
+                    return Err(ResolveError::ReadInOwnInitializer {
+                        name: name.to_string(),
+                        line: *line,
+                    });
+                }
+                self.resolve_local(name, *id);
+                Ok(())
+            }
+            Expr::Assign {
+                name,
+                value,
+                id,
+                line,
+            } => {
+                self.check_assignable(name, *line)?;
+                self.resolve_expr(value)?;
+                self.resolve_local(name, *id);
+                Ok(())
+            }
+            Expr::Call {
+                callee, arguments, ..
+            } => {
+                self.resolve_expr(callee)?;
+                for argument in arguments {
+                    self.resolve_expr(argument)?;
+                }
+                Ok(())
+            }
+            Expr::Get { object, .. } => self.resolve_expr(object),
+            Expr::Set { object, value, .. } => {
+                self.resolve_expr(value)?;
+                self.resolve_expr(object)
+            }
+            Expr::Interpolation(parts) => {
+                for part in parts {
+                    self.resolve_expr(part)?;
+                }
+                Ok(())
+            }
+            Expr::List(elements) => {
+                for element in elements {
+                    self.resolve_expr(element)?;
+                }
+                Ok(())
+            }
+            Expr::Map { entries, .. } => {
+                for (key, value) in entries {
+                    self.resolve_expr(key)?;
+                    self.resolve_expr(value)?;
+                }
+                Ok(())
+            }
+            Expr::Index { object, index, .. } => {
+                self.resolve_expr(object)?;
+                self.resolve_expr(index)
+            }
+            Expr::IndexSet {
+                object,
+                index,
+                value,
+                ..
+            } => {
+                self.resolve_expr(object)?;
+                self.resolve_expr(index)?;
+                self.resolve_expr(value)
+            }
+            Expr::CompoundAssign {
+                target,
+                value,
+                line,
+                ..
+            } => {
+                self.check_target_assignable(target, *line)?;
+                self.resolve_expr(target)?;
+                self.resolve_expr(value)
+            }
+            Expr::Increment { target, line, .. } => {
+                self.check_target_assignable(target, *line)?;
+                self.resolve_expr(target)
+            }
+            Expr::This { id, line } => {
+                if self.current_class == ClassType::None {
+                    return Err(ResolveError::ThisOutsideClass { line: *line });
+                }
+                self.resolve_local("this", *id);
+                Ok(())
+            }
+            Expr::Super { id, line, .. } => match self.current_class {
+                ClassType::None => Err(ResolveError::SuperOutsideClass { line: *line }),
+                ClassType::Class => Err(ResolveError::SuperWithoutSuperclass { line: *line }),
+                ClassType::Subclass => {
+                    self.resolve_local("super", *id);
+                    Ok(())
+                }
+            },
+        }
+    }
+}
+
+//the top level const and let names of the module at path, plus the constants it imports itself.
+//a module that can't be read or parsed has none, the interpreter reports it once it loads the file
+fn module_constants(path: &Path, visited: &mut HashSet<PathBuf>) -> HashSet<String> {
+    let mut constants: HashSet<String> = HashSet::new();
+    let path: PathBuf = match fs::canonicalize(path) {
+        Ok(path) if visited.insert(path.clone()) => path,
+        _ => return constants,
+    };
+    let statements: Vec<Stmt> = match fs::read_to_string(&path)
+        .ok()
+        .and_then(|source| lex_program(&source).ok())
+        .and_then(|tokens| parse_tokens(&tokens).ok())
+    {
+        Some(statements) => statements,
+        None => return constants,
+    };
+
+    let directory: PathBuf = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    for statement in statements.iter() {
+        match statement {
+            Stmt::Var {
+                name,
+                mutable: false,
+                ..
+            } => {
+                constants.insert(name.to_string());
+            }
+            Stmt::Import {
+                path,
+                names,
+                alias: None,
+                ..
+            } => {
+                let imported = module_constants(&directory.join(path), visited);
+                constants.extend(
+                    names
+                        .iter()
+                        .filter(|name| imported.contains(*name))
+                        .cloned(),
+                );
+            }
+            _ => (),
+        }
+    }
+    constants
+}
+
+//path is the file the statements came from, None for the prompt
+pub fn resolve(
+    statements: &Vec<Stmt>,
+    path: Option<&Path>,
+) -> Result<HashMap<usize, usize>, ResolveError> {
+    let directory: PathBuf = path
+        .and_then(|path| path.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let mut resolver: Resolver = Resolver::new(directory);
+    resolver.resolve_statements(statements)?;
+    Ok(resolver.locals)
+}